@@ -0,0 +1,76 @@
+use crate::solver::{Answer, Part};
+use anyhow::Result;
+use config::{Config, File, FileFormat};
+use std::collections::BTreeMap;
+use std::fs;
+
+pub const DEFAULT_ANSWERS_FILE: &str = "input/answers.toml";
+
+/// Answers recorded from previous runs, stored as a toml file with one table per day:
+///
+/// ```toml
+/// [day7]
+/// part1 = "355150"
+/// part2 = "98368490"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    path: String,
+    days: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Check {
+    Match,
+    Mismatch { expected: String, actual: String },
+    NotRecorded,
+}
+
+impl Answers {
+    /// Loads the answers file. A missing file is treated as having no recorded answers.
+    pub fn load(path: &str) -> Result<Answers> {
+        let mut settings = Config::default();
+        settings.merge(File::new(path, FileFormat::Toml).required(false))?;
+
+        Ok(Answers {
+            path: path.to_owned(),
+            days: settings.try_into()?,
+        })
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&String> {
+        self.days.get(&day_key(day))?.get(&part_key(part))
+    }
+
+    pub fn set(&mut self, day: u32, part: Part, answer: &Answer) {
+        self.days
+            .entry(day_key(day))
+            .or_default()
+            .insert(part_key(part), answer.to_string());
+    }
+
+    pub fn check(&self, day: u32, part: Part, answer: &Answer) -> Check {
+        let actual = answer.to_string();
+        match self.get(day, part) {
+            Some(expected) if *expected == actual => Check::Match,
+            Some(expected) => Check::Mismatch {
+                expected: expected.clone(),
+                actual,
+            },
+            None => Check::NotRecorded,
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, toml::to_string(&self.days)?)?;
+        Ok(())
+    }
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{}", day)
+}
+
+fn part_key(part: Part) -> String {
+    format!("part{}", part)
+}
@@ -1,51 +1,476 @@
-use argparse::{ArgumentParser, Store, StoreOption};
+use crate::answers::DEFAULT_ANSWERS_FILE;
+use crate::cache::{cached_input, YEAR};
+use crate::common::render::{Render, RenderFormat};
+use crate::day12::{Export, RevisitPolicy};
+use crate::day13::Frames;
+use crate::day16::NumericMode;
+use crate::io::{input_path, STDIN};
+use crate::output::OutputFormat;
+use crate::settings::Settings;
+use anyhow::Result;
+use argparse::{ArgumentParser, DecrBy, IncrBy, Store, StoreOption, StoreTrue};
+use std::str::FromStr;
+use std::{env, io, process};
+
+#[derive(Debug)]
+pub enum Command {
+    Solve(Box<Arguments>),
+    Cache(CacheArguments),
+    Bits(BitsArguments),
+}
+
+impl Command {
+    /// Solving is the default; `cache` as the first argument manages the input cache instead, and
+    /// `bits` decodes day 16 transmissions interactively.
+    pub fn parse_args() -> Result<Command> {
+        let mut args = env::args().collect::<Vec<String>>();
+        match args.get(1).map(String::as_str) {
+            Some("cache") => {
+                let command = args.remove(1);
+                args[0] = format!("{} {}", args[0], command);
+                return Ok(Command::Cache(CacheArguments::parse(args)?));
+            }
+            Some("bits") => {
+                let command = args.remove(1);
+                args[0] = format!("{} {}", args[0], command);
+                return Ok(Command::Bits(BitsArguments::parse(args)?));
+            }
+            _ => {}
+        }
+
+        Ok(Command::Solve(Box::new(Arguments::parse_args()?)))
+    }
+
+    pub fn verbosity(&self) -> i32 {
+        match self {
+            Command::Solve(args) => args.verbosity,
+            Command::Cache(args) => args.verbosity,
+            Command::Bits(args) => args.verbosity,
+        }
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct Arguments {
-    pub day: u32,
-    pub part: u32,
+    pub days: Option<DayRange>,
+    pub all: bool,
+    pub part: Option<u32>,
     pub src_file: Option<String>,
+    pub variant: Option<String>,
+    pub input_dir: Option<String>,
+    pub check: bool,
+    pub record: bool,
+    pub answers_file: String,
+    pub bench: Option<usize>,
+    pub bench_output: Option<String>,
+    pub output: Option<OutputFormat>,
+    pub render: Option<RenderFormat>,
+    pub render_dir: Option<String>,
+    pub tile_factor: Option<usize>,
+    pub list_paths: bool,
+    pub policy: Option<RevisitPolicy>,
+    pub export_dir: Option<String>,
+    pub edge_usage: bool,
+    pub frames: Option<Frames>,
+    pub numeric: Option<NumericMode>,
+    pub verbosity: i32,
+}
+
+/// Where renders go unless `--render-dir` says otherwise.
+pub const DEFAULT_RENDER_DIR: &str = "render";
+
+/// An inclusive range of days, e.g. `3..9` or just `7`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct DayRange {
+    pub first: u32,
+    pub last: u32,
+}
+
+impl DayRange {
+    pub fn contains(&self, day: u32) -> bool {
+        self.first <= day && day <= self.last
+    }
+
+    pub fn is_single_day(&self) -> bool {
+        self.first == self.last
+    }
+}
+
+impl FromStr for DayRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<DayRange> {
+        let (first, last) = match s.split_once("..") {
+            Some((first, last)) => (first, last.trim_start_matches('=')),
+            None => (s, s),
+        };
+        let first: u32 = first.trim().parse()?;
+        let last: u32 = last.trim().parse()?;
+
+        if first > last {
+            return Err(anyhow::Error::msg(format!("Invalid day range: {}", s)));
+        }
+
+        Ok(DayRange { first, last })
+    }
 }
 
 impl Arguments {
-    pub fn parse_args() -> Arguments {
-        let mut args = Arguments::default();
+    pub fn parse_args() -> Result<Arguments> {
+        let mut args = Arguments {
+            answers_file: DEFAULT_ANSWERS_FILE.to_owned(),
+            ..Default::default()
+        };
 
         {
             let mut parser = ArgumentParser::new();
-            parser.set_description("Advent of Code 2020");
+            parser.set_description("Advent of Code 2021");
+
+            parser.refer(&mut args.days).add_option(
+                &["-n", "--day"],
+                StoreOption,
+                "Number of the Advent of Code challenge/day, or an inclusive range of days such as '3..9'",
+            );
+
+            parser.refer(&mut args.all).add_option(
+                &["-a", "--all"],
+                StoreTrue,
+                "Run every registered day",
+            );
+
+            parser.refer(&mut args.part).add_option(
+                &["-p", "--part"],
+                StoreOption,
+                "Each problem has two parts -- specific the part with this option. Runs both parts if omitted",
+            );
+
+            parser.refer(&mut args.src_file).add_option(
+                &["-i", "--input", "--input-file"],
+                StoreOption,
+                "Input file, or '-' to read from stdin. Defaults to '<input dir>/day<number>.input'",
+            );
+
+            parser.refer(&mut args.variant).add_option(
+                &["--variant"],
+                StoreOption,
+                "Use '<input dir>/day<number>-<variant>.input' instead, e.g. '--variant test'",
+            );
+
+            parser.refer(&mut args.input_dir).add_option(
+                &["--input-dir"],
+                StoreOption,
+                "Directory with the input files. Overrides the input_dir setting, which defaults to 'input'",
+            );
+
+            parser.refer(&mut args.check).add_option(
+                &["-c", "--check"],
+                StoreTrue,
+                "Compare answers against the answers file and fail on any mismatch",
+            );
+
+            parser.refer(&mut args.record).add_option(
+                &["-r", "--record"],
+                StoreTrue,
+                "Write the computed answers into the answers file",
+            );
+
+            parser.refer(&mut args.answers_file).add_option(
+                &["--answers-file"],
+                Store,
+                "File with the expected answers. Defaults to 'input/answers.toml'",
+            );
+
+            parser.refer(&mut args.bench).add_option(
+                &["-b", "--bench"],
+                StoreOption,
+                "Benchmark mode: parse and solve each selected day and part this many times",
+            );
+
+            parser.refer(&mut args.bench_output).add_option(
+                &["--bench-output"],
+                StoreOption,
+                "Also write the benchmark results to this file, as JSON if it ends in '.json' and CSV otherwise",
+            );
+
+            parser.refer(&mut args.output).add_option(
+                &["-o", "--output"],
+                StoreOption,
+                "How to write answers to stdout: table, plain, json or tsv. Defaults to plain for a single day and part, table otherwise",
+            );
+
+            parser.refer(&mut args.render).add_option(
+                &["--render"],
+                StoreOption,
+                "Write a picture of the solution for days that support it (15): ascii, ppm or svg",
+            );
+
+            parser.refer(&mut args.render_dir).add_option(
+                &["--render-dir"],
+                StoreOption,
+                "Directory to write renders to. Defaults to 'render'",
+            );
+
+            parser.refer(&mut args.list_paths).add_option(
+                &["--list-paths"],
+                StoreTrue,
+                "Day 12: walk every path and log it, instead of only counting them",
+            );
+
+            parser.refer(&mut args.policy).add_option(
+                &["--policy"],
+                StoreOption,
+                "Day 12: which caves paths may revisit, for both parts. Comma separated settings from: part1, part2, revisits=N, revisits:<cave>=N, caves=N, forbid=<cave>, start, end",
+            );
+
+            parser.refer(&mut args.export_dir).add_option(
+                &["--export-dir"],
+                StoreOption,
+                "Day 12: write the cave system for each part to this directory, as Graphviz DOT and as JSON",
+            );
+
+            parser.refer(&mut args.edge_usage).add_option(
+                &["--edge-usage"],
+                StoreTrue,
+                "Day 12: with --export-dir, walk every path and include how many use each tunnel",
+            );
+
+            parser.refer(&mut args.frames).add_option(
+                &["--frames"],
+                StoreOption,
+                "Day 13: write the sheet after each fold, to '-' for stderr or to numbered files in this directory",
+            );
+
+            parser.refer(&mut args.tile_factor).add_option(
+                &["--tile-factor"],
+                StoreOption,
+                "Day 15: how many copies of the cave make up the full map in each direction. Defaults to 5",
+            );
+
+            parser.refer(&mut args.numeric).add_option(
+                &["--numeric"],
+                StoreOption,
+                "Day 16: what to do when a value overflows 64 bits: 'checked' fails with an error, 'big' uses arbitrary precision. Defaults to checked",
+            );
 
             parser
-                .refer(&mut args.day)
-                .add_option(
-                    &["-n", "--day"],
-                    Store,
-                    "Number of the Advent of Code challenge/day",
-                )
-                .required();
+                .refer(&mut args.verbosity)
+                .add_option(&["-v", "--verbose"], IncrBy(1), "Log more. Can be repeated")
+                .add_option(&["-q", "--quiet"], DecrBy(1), "Log less. Can be repeated");
+
+            parser.parse_args_or_exit();
+        }
+
+        if args.all == args.days.is_some() {
+            return Err(anyhow::Error::msg("Specify exactly one of --day or --all"));
+        }
+
+        if args.src_file.is_some() && !args.is_single_day() {
+            return Err(anyhow::Error::msg(
+                "--input-file can only be used when running a single day",
+            ));
+        }
+
+        if args.src_file.is_some() && args.variant.is_some() {
+            return Err(anyhow::Error::msg(
+                "--input-file and --variant can't be used together",
+            ));
+        }
+
+        if args.bench_output.is_some() && args.bench.is_none() {
+            return Err(anyhow::Error::msg("--bench-output requires --bench"));
+        }
+
+        if args.render_dir.is_some() && args.render.is_none() {
+            return Err(anyhow::Error::msg("--render-dir requires --render"));
+        }
+
+        if args.edge_usage && args.export_dir.is_none() {
+            return Err(anyhow::Error::msg("--edge-usage requires --export-dir"));
+        }
+
+        if args.tile_factor == Some(0) {
+            return Err(anyhow::Error::msg("--tile-factor must be at least 1"));
+        }
+
+        Ok(args)
+    }
+
+    pub fn is_single_day(&self) -> bool {
+        self.days.is_some_and(|days| days.is_single_day())
+    }
+
+    /// Where and how to write renders, if they were asked for.
+    pub fn render(&self) -> Option<Render> {
+        let dir = self.render_dir.as_deref().unwrap_or(DEFAULT_RENDER_DIR);
+        self.render.map(|format| Render::new(format, dir))
+    }
+
+    /// Where to export the day 12 cave system, if it was asked for.
+    pub fn export(&self) -> Option<Export> {
+        self.export_dir
+            .as_deref()
+            .map(|dir| Export::new(dir, self.edge_usage))
+    }
+
+    pub fn reads_stdin(&self) -> bool {
+        self.src_file.as_deref() == Some(STDIN)
+    }
+
+    /// The input file for the given day: the one passed on the command line, the cached input if
+    /// there is one, or the default one in the input directory. `None` means the input should be
+    /// read from stdin.
+    pub fn input_file(&self, day: u32, settings: &Settings) -> Option<String> {
+        if self.reads_stdin() {
+            return None;
+        }
+        if let Some(src_file) = &self.src_file {
+            return Some(src_file.clone());
+        }
+
+        if self.variant.is_none() && self.input_dir.is_none() {
+            if let Some(path) = cached_input(settings, day) {
+                return Some(path);
+            }
+        }
+
+        let input_dir = self.input_dir.as_deref().unwrap_or(&settings.input_dir);
+        Some(input_path(input_dir, day, self.variant.as_deref()))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CacheAction {
+    Import,
+    List,
+}
+
+impl FromStr for CacheAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<CacheAction> {
+        match s {
+            "import" => Ok(CacheAction::Import),
+            "list" => Ok(CacheAction::List),
+            _ => Err(anyhow::Error::msg(format!(
+                "Unrecognized cache action {}",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CacheArguments {
+    pub action: CacheAction,
+    pub day: Option<u32>,
+    pub year: u32,
+    pub profile: Option<String>,
+    pub src_file: Option<String>,
+    pub verbosity: i32,
+}
+
+impl CacheArguments {
+    fn parse(args: Vec<String>) -> Result<CacheArguments> {
+        let mut cache_args = CacheArguments {
+            action: CacheAction::List,
+            day: None,
+            year: YEAR,
+            profile: None,
+            src_file: None,
+            verbosity: 0,
+        };
+
+        {
+            let mut parser = ArgumentParser::new();
+            parser.set_description("Manage the cache of puzzle inputs");
 
             parser
-                .refer(&mut args.part)
-                .add_option(
-                    &["-p", "--part"],
+                .refer(&mut cache_args.action)
+                .add_argument(
+                    "action",
                     Store,
-                    "Each problem has two parts -- specific the part with this option",
+                    "'import' a file, or 'list' the cached inputs",
                 )
                 .required();
 
-            parser.refer(&mut args.src_file).add_option(
-                &["-i", "--input-file"],
+            parser.refer(&mut cache_args.src_file).add_argument(
+                "file",
                 StoreOption,
-                "Input file. Defaults to 'input/day<number>.input",
+                "The input file to import",
             );
 
-            parser.parse_args_or_exit();
+            parser.refer(&mut cache_args.day).add_option(
+                &["-n", "--day"],
+                StoreOption,
+                "Number of the Advent of Code challenge/day the input is for",
+            );
+
+            parser.refer(&mut cache_args.year).add_option(
+                &["--year"],
+                Store,
+                "Year the input is for. Defaults to 2021",
+            );
+
+            parser.refer(&mut cache_args.profile).add_option(
+                &["--profile"],
+                StoreOption,
+                "Profile the input belongs to. Overrides the profile setting",
+            );
+
+            parser
+                .refer(&mut cache_args.verbosity)
+                .add_option(&["-v", "--verbose"], IncrBy(1), "Log more. Can be repeated")
+                .add_option(&["-q", "--quiet"], DecrBy(1), "Log less. Can be repeated");
+
+            if let Err(code) = parser.parse(args, &mut io::stdout(), &mut io::stderr()) {
+                process::exit(code);
+            }
         }
 
-        if args.src_file.is_none() {
-            args.src_file = Some(format!("input/day{}.input", args.day));
+        if cache_args.action == CacheAction::Import
+            && (cache_args.day.is_none() || cache_args.src_file.is_none())
+        {
+            return Err(anyhow::Error::msg(
+                "Importing an input requires --day and a file",
+            ));
+        }
+
+        Ok(cache_args)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BitsArguments {
+    pub src_file: Option<String>,
+    pub verbosity: i32,
+}
+
+impl BitsArguments {
+    fn parse(args: Vec<String>) -> Result<BitsArguments> {
+        let mut bits_args = BitsArguments::default();
+
+        {
+            let mut parser = ArgumentParser::new();
+            parser.set_description(
+                "Decode day 16 BITS transmissions, one hex string per line, and print the version sum, value and packet tree of each",
+            );
+
+            parser.refer(&mut bits_args.src_file).add_argument(
+                "file",
+                StoreOption,
+                "File with the transmissions. Reads from stdin if omitted",
+            );
+
+            parser
+                .refer(&mut bits_args.verbosity)
+                .add_option(&["-v", "--verbose"], IncrBy(1), "Log more. Can be repeated")
+                .add_option(&["-q", "--quiet"], DecrBy(1), "Log less. Can be repeated");
+
+            if let Err(code) = parser.parse(args, &mut io::stdout(), &mut io::stderr()) {
+                process::exit(code);
+            }
         }
 
-        args
+        Ok(bits_args)
     }
 }
@@ -0,0 +1,165 @@
+use crate::io::PuzzleInput;
+use crate::solver::{DynSolver, Part};
+use anyhow::Result;
+use serde_derive::Serialize;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Timing statistics over all iterations of a benchmark, in nanoseconds.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut samples = samples
+            .iter()
+            .map(|d| d.as_nanos() as u64)
+            .collect::<Vec<u64>>();
+        samples.sort_unstable();
+
+        if samples.is_empty() {
+            return Stats {
+                min_ns: 0,
+                median_ns: 0,
+                mean_ns: 0,
+                max_ns: 0,
+            };
+        }
+
+        let middle = samples.len() / 2;
+        let median_ns = if samples.len() % 2 == 0 {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Stats {
+            min_ns: samples[0],
+            median_ns,
+            mean_ns: samples.iter().sum::<u64>() / samples.len() as u64,
+            max_ns: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Benchmark {
+    pub day: u32,
+    pub part: u32,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+/// Parses and solves the given day and part `iterations` times. The input is passed in already
+/// read, so file IO isn't part of the parse timings. `name` is only used for parse errors.
+pub fn bench(
+    solver: &dyn DynSolver,
+    day: u32,
+    part: Part,
+    name: &str,
+    contents: &[u8],
+    iterations: usize,
+) -> Result<Benchmark> {
+    let mut parse_times = Vec::with_capacity(iterations);
+    let mut solve_times = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let input = PuzzleInput::from_contents(name, contents);
+
+        let start = Instant::now();
+        let input = solver.parse(input)?;
+        parse_times.push(start.elapsed());
+
+        let start = Instant::now();
+        solver.solve_parsed(part, input)?;
+        solve_times.push(start.elapsed());
+    }
+
+    Ok(Benchmark {
+        day,
+        part: part.number(),
+        iterations,
+        parse: Stats::from_samples(&parse_times),
+        solve: Stats::from_samples(&solve_times),
+    })
+}
+
+/// Renders a table with a parse row and a solve row for each benchmark.
+pub fn bench_table(benchmarks: &[Benchmark]) -> String {
+    let mut lines = vec![format!(
+        "{:<3} | {:<4} | {:<5} | {:>6} | {:>12} | {:>12} | {:>12} | {:>12}",
+        "Day", "Part", "Step", "Runs", "Min", "Median", "Mean", "Max"
+    )];
+    lines.push(format!(
+        "{}-+-{}-+-{}-+-{}-+-{}-+-{}-+-{}-+-{}",
+        "-".repeat(3),
+        "-".repeat(4),
+        "-".repeat(5),
+        "-".repeat(6),
+        "-".repeat(12),
+        "-".repeat(12),
+        "-".repeat(12),
+        "-".repeat(12)
+    ));
+
+    for benchmark in benchmarks {
+        for (step, stats) in [("parse", &benchmark.parse), ("solve", &benchmark.solve)] {
+            lines.push(format!(
+                "{:<3} | {:<4} | {:<5} | {:>6} | {:>12} | {:>12} | {:>12} | {:>12}",
+                benchmark.day,
+                benchmark.part,
+                step,
+                benchmark.iterations,
+                format_nanos(stats.min_ns),
+                format_nanos(stats.median_ns),
+                format_nanos(stats.mean_ns),
+                format_nanos(stats.max_ns),
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn format_nanos(nanos: u64) -> String {
+    format!("{:.3?}", Duration::from_nanos(nanos))
+}
+
+/// Writes the benchmarks to a file. The format is picked from the extension: `.json` writes a
+/// JSON array, anything else is written as CSV.
+pub fn write_benchmarks(path: &str, benchmarks: &[Benchmark]) -> Result<()> {
+    let is_json = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension == "json");
+
+    let contents = if is_json {
+        serde_json::to_string_pretty(benchmarks)?
+    } else {
+        let mut lines = vec!["day,part,step,iterations,min_ns,median_ns,mean_ns,max_ns".to_owned()];
+        for benchmark in benchmarks {
+            for (step, stats) in [("parse", &benchmark.parse), ("solve", &benchmark.solve)] {
+                lines.push(format!(
+                    "{},{},{},{},{},{},{},{}",
+                    benchmark.day,
+                    benchmark.part,
+                    step,
+                    benchmark.iterations,
+                    stats.min_ns,
+                    stats.median_ns,
+                    stats.mean_ns,
+                    stats.max_ns
+                ));
+            }
+        }
+        lines.join("\n") + "\n"
+    };
+
+    fs::write(path, contents)?;
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use crate::settings::Settings;
+use anyhow::{Context, Result};
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const YEAR: u32 = 2021;
+
+const MANIFEST_FILE: &str = "manifest.toml";
+
+/// Where a cached input came from, and what it looked like when it was imported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub checksum: String,
+    pub source: String,
+    /// Seconds since the unix epoch
+    pub imported_at: u64,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Status {
+    Ok,
+    Modified,
+    Missing,
+}
+
+/// Puzzle inputs for one year and profile, stored as '<cache dir>/<year>/<profile>/day<N>.input'
+/// with a manifest next to them recording the checksum and source of each file.
+#[derive(Debug)]
+pub struct InputCache {
+    dir: PathBuf,
+    manifest: BTreeMap<String, Entry>,
+}
+
+impl InputCache {
+    pub fn open(cache_dir: &str, year: u32, profile: &str) -> Result<InputCache> {
+        let dir = Path::new(cache_dir).join(year.to_string()).join(profile);
+
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let manifest = if manifest_path.exists() {
+            let contents = fs::read_to_string(&manifest_path)?;
+            toml::from_str(&contents)
+                .with_context(|| format!("Invalid manifest {}", manifest_path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(InputCache { dir, manifest })
+    }
+
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{}.input", day))
+    }
+
+    pub fn entry(&self, day: u32) -> Option<&Entry> {
+        self.manifest.get(&day_key(day))
+    }
+
+    /// The cached days and their manifest entries, in order.
+    pub fn entries(&self) -> Vec<(u32, &Entry)> {
+        let mut entries = self
+            .manifest
+            .iter()
+            .filter_map(|(key, entry)| Some((key.strip_prefix("day")?.parse().ok()?, entry)))
+            .collect::<Vec<(u32, &Entry)>>();
+        entries.sort_by_key(|(day, _)| *day);
+        entries
+    }
+
+    /// Copies the input into the cache, normalizing line endings and the trailing newline, and
+    /// records it in the manifest. Re-importing a day replaces it.
+    pub fn import(&mut self, day: u32, source: &str) -> Result<PathBuf> {
+        let contents =
+            fs::read_to_string(source).with_context(|| format!("Unable to open {}", source))?;
+        let contents = normalize(&contents);
+
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(day);
+        fs::write(&path, &contents)?;
+
+        let source = fs::canonicalize(source)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| source.to_owned());
+        let entry = Entry {
+            checksum: checksum(contents.as_bytes()),
+            source,
+            imported_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+        self.manifest.insert(day_key(day), entry);
+        self.save()?;
+
+        Ok(path)
+    }
+
+    pub fn status(&self, day: u32) -> Result<Status> {
+        let entry = match self.entry(day) {
+            Some(entry) => entry,
+            None => return Ok(Status::Missing),
+        };
+        let path = self.path(day);
+        if !path.exists() {
+            return Ok(Status::Missing);
+        }
+
+        if checksum(&fs::read(path)?) == entry.checksum {
+            Ok(Status::Ok)
+        } else {
+            Ok(Status::Modified)
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        fs::write(
+            self.dir.join(MANIFEST_FILE),
+            toml::to_string(&self.manifest)?,
+        )?;
+        Ok(())
+    }
+}
+
+/// The cached input for the given day, if there is one. Warns if the cached file no longer
+/// matches the checksum it was imported with.
+pub fn cached_input(settings: &Settings, day: u32) -> Option<String> {
+    let cache = match InputCache::open(&settings.cache_dir, YEAR, &settings.profile) {
+        Ok(cache) => cache,
+        Err(e) => {
+            warn!("Unable to open the input cache: {:#}", e);
+            return None;
+        }
+    };
+
+    let path = cache.path(day).display().to_string();
+    match cache.status(day) {
+        Ok(Status::Ok) => Some(path),
+        Ok(Status::Modified) => {
+            warn!(
+                "{} has changed since it was imported from {}",
+                path,
+                cache.entry(day).unwrap().source
+            );
+            Some(path)
+        }
+        Ok(Status::Missing) => None,
+        Err(e) => {
+            warn!("Unable to check {}: {:#}", path, e);
+            Some(path)
+        }
+    }
+}
+
+/// Uses '\n' line endings and ends the input with exactly one newline.
+pub fn normalize(contents: &str) -> String {
+    let contents = contents.replace("\r\n", "\n").replace('\r', "\n");
+    let mut contents = contents.trim_end_matches('\n').to_owned();
+    contents.push('\n');
+    contents
+}
+
+pub fn checksum(contents: &[u8]) -> String {
+    format!("sha256:{:x}", Sha256::digest(contents))
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{}", day)
+}
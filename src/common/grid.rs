@@ -0,0 +1,246 @@
+use crate::common::point::{Point2, Position};
+use crate::io::PuzzleInput;
+use anyhow::Result;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid stored row by row in a single `Vec`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Panics if `cells` doesn't hold exactly `width * height` values.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "A {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Grid<T>
+    where
+        F: FnMut(Position) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Position::new(x, y)))
+            .map(&mut f)
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(anyhow::Error::msg(format!(
+                "Row {} has {} cells but the first row has {}",
+                y + 1,
+                row.len(),
+                width
+            )));
+        }
+
+        Ok(Grid::new(
+            width,
+            height,
+            rows.into_iter().flatten().collect(),
+        ))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, p: Position) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Position) -> Option<&T> {
+        if self.contains(p) {
+            self.cells.get(self.index_of(p))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Position) -> Option<&mut T> {
+        if self.contains(p) {
+            let i = self.index_of(p);
+            self.cells.get_mut(i)
+        } else {
+            None
+        }
+    }
+
+    /// Index of `p` in the backing store.
+    pub fn index_of(&self, p: Position) -> usize {
+        p.y * self.width + p.x
+    }
+
+    /// Position of the cell at `index` in the backing store.
+    pub fn position_of(&self, index: usize) -> Position {
+        Position::new(index % self.width, index / self.width)
+    }
+
+    /// Every position in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Position::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// The up to 4 positions directly above, below, left and right of `p`.
+    pub fn neighbors4(&self, p: Position) -> impl Iterator<Item = Position> + '_ {
+        Point2::<isize>::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |d| self.offset(p, d))
+    }
+
+    /// The up to 8 positions around `p`, including diagonals.
+    pub fn neighbors8(&self, p: Position) -> impl Iterator<Item = Position> + '_ {
+        Point2::<isize>::ALL_DIRECTIONS
+            .into_iter()
+            .filter_map(move |d| self.offset(p, d))
+    }
+
+    /// `p` moved by `d`, if that's still inside the grid.
+    pub fn offset(&self, p: Position, d: Point2<isize>) -> Option<Position> {
+        let moved = Position::new(p.x.checked_add_signed(d.x)?, p.y.checked_add_signed(d.y)?);
+        if self.contains(moved) {
+            Some(moved)
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // not chunks, which would yield nothing for a grid with rows but no columns
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U, F>(self, f: F) -> Grid<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.into_iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: From<u8>,
+{
+    /// One row per line, with every character a single digit.
+    pub fn from_digit_lines(input: PuzzleInput) -> Result<Grid<T>> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.lines() {
+            let line = line?;
+            let row = line.digits()?;
+            match width {
+                None => width = Some(row.len()),
+                Some(width) if width != row.len() => {
+                    return Err(line
+                        .error_at(
+                            width.min(row.len()) + 1,
+                            format!("Expected {} digits but got {}", width, row.len()),
+                        )
+                        .into());
+                }
+                _ => {}
+            }
+            cells.extend(row);
+            height += 1;
+        }
+
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Position) -> &T {
+        assert!(self.contains(p), "{:?} is outside the grid", p);
+        &self.cells[self.index_of(p)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, p: Position) -> &mut T {
+        assert!(self.contains(p), "{:?} is outside the grid", p);
+        let i = self.index_of(p);
+        &mut self.cells[i]
+    }
+}
+
+impl<T> fmt::Display for Grid<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1 +1,5 @@
-pub mod position;
+pub mod grid;
+pub mod parse;
+pub mod point;
+pub mod render;
+pub mod search;
@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// A problem with the puzzle input, pointing at where in the input it is.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub file: String,
+    /// 1-based
+    pub line: usize,
+    /// 1-based, in characters
+    pub column: usize,
+    /// The offending line
+    pub text: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}:{}:{}: {}",
+            self.file, self.line, self.column, self.message
+        )?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {}^", " ".repeat(self.column.saturating_sub(1)))
+    }
+}
+
+impl Error for ParseError {}
+
+/// One line of puzzle input, along with where it came from.
+#[derive(Debug, Clone)]
+pub struct InputLine {
+    pub file: Arc<str>,
+    /// 1-based
+    pub number: usize,
+    pub text: String,
+}
+
+impl InputLine {
+    pub fn new(file: Arc<str>, number: usize, text: String) -> InputLine {
+        InputLine { file, number, text }
+    }
+
+    /// 1-based column of `field`, which should be a slice of this line's text. Falls back to the
+    /// start of the line if it isn't.
+    pub fn column_of(&self, field: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let field_start = field.as_ptr() as usize;
+        if field_start < start || field_start > start + self.text.len() {
+            return 1;
+        }
+        self.text[..field_start - start].chars().count() + 1
+    }
+
+    /// An error pointing at `field`, which should be a slice of this line's text.
+    pub fn error<M>(&self, field: &str, message: M) -> ParseError
+    where
+        M: Into<String>,
+    {
+        self.error_at(self.column_of(field), message)
+    }
+
+    pub fn error_at<M>(&self, column: usize, message: M) -> ParseError
+    where
+        M: Into<String>,
+    {
+        ParseError {
+            file: self.file.to_string(),
+            line: self.number,
+            column,
+            text: self.text.clone(),
+            message: message.into(),
+        }
+    }
+
+    /// Parses `field`, which should be a slice of this line's text.
+    pub fn parse<T>(&self, field: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        field
+            .parse()
+            .map_err(|e| self.error(field, format!("Unable to parse '{}': {}", field, e)))
+    }
+
+    /// Splits `field` on the first occurrence of `separator`.
+    pub fn split_once<'a>(
+        &self,
+        field: &'a str,
+        separator: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        field
+            .split_once(separator)
+            .ok_or_else(|| self.error(field, format!("Expected '{}'", separator)))
+    }
+
+    /// Every character of the line as a single digit.
+    pub fn digits<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: From<u8>,
+    {
+        self.text
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                c.to_digit(10).map(|d| T::from(d as u8)).ok_or_else(|| {
+                    self.error_at(i + 1, format!("Expected a digit but got '{}'", c))
+                })
+            })
+            .collect()
+    }
+}
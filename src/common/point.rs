@@ -0,0 +1,391 @@
+use crate::common::parse::{InputLine, ParseError};
+use anyhow::Result;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A grid position. Grids are indexed from the top left, so y increases downwards.
+pub type Position = Point2<usize>;
+
+/// An integer type that can be used for the coordinates of a point.
+pub trait Coordinate:
+    Copy
+    + Ord
+    + Default
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+pub trait SignedCoordinate: Coordinate + Neg<Output = Self> {
+    const MINUS_ONE: Self;
+}
+
+macro_rules! coordinate {
+    ($($t:ty),*) => {
+        $(impl Coordinate for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+        })*
+    };
+}
+
+macro_rules! signed_coordinate {
+    ($($t:ty),*) => {
+        $(impl SignedCoordinate for $t {
+            const MINUS_ONE: $t = -1;
+        })*
+    };
+}
+
+coordinate!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+signed_coordinate!(i8, i16, i32, i64, isize);
+
+/// `|a - b|` without underflowing unsigned types.
+fn distance<T: Coordinate>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+fn signum<T: SignedCoordinate>(a: T) -> T {
+    match a.cmp(&T::ZERO) {
+        std::cmp::Ordering::Less => T::MINUS_ONE,
+        std::cmp::Ordering::Equal => T::ZERO,
+        std::cmp::Ordering::Greater => T::ONE,
+    }
+}
+
+/// Parses `field`, a slice of `line`, as `count` comma separated numbers.
+fn parse_fields<T>(line: &InputLine, field: &str, count: usize) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let parts = field.split(',').collect::<Vec<&str>>();
+    if parts.len() != count {
+        return Err(line.error(field, format!("Expected {} comma separated numbers", count)));
+    }
+    parts.into_iter().map(|s| line.parse(s.trim())).collect()
+}
+
+/// Parses `s` as `count` comma separated numbers.
+fn parse_str<T>(s: &str, count: usize) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let parts = s.split(',').collect::<Vec<&str>>();
+    if parts.len() != count {
+        return Err(anyhow::Error::msg(format!(
+            "Expected {} comma separated numbers but got '{}'",
+            count, s
+        )));
+    }
+    parts
+        .into_iter()
+        .map(|part| {
+            part.trim().parse().map_err(|e| {
+                anyhow::Error::msg(format!("Unable to parse '{}': {}", part.trim(), e))
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+
+    /// Converts each coordinate to another integer type, if they all fit.
+    pub fn try_cast<U>(self) -> Option<Point2<U>>
+    where
+        U: TryFrom<T>,
+    {
+        Some(Point2::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
+    }
+
+    /// Parses `field`, a slice of `line` in the form 'x,y'.
+    pub fn from_input(line: &InputLine, field: &str) -> Result<Point2<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let mut fields = parse_fields(line, field, 2)?.into_iter();
+        Ok(Point2::new(fields.next().unwrap(), fields.next().unwrap()))
+    }
+}
+
+impl<T: Coordinate> Point2<T> {
+    pub const ORIGIN: Point2<T> = Point2::new(T::ZERO, T::ZERO);
+
+    pub fn manhattan_distance(self, other: Point2<T>) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// The number of king moves between the two points.
+    pub fn chebyshev_distance(self, other: Point2<T>) -> T {
+        distance(self.x, other.x).max(distance(self.y, other.y))
+    }
+}
+
+impl<T: SignedCoordinate> Point2<T> {
+    pub const UP: Point2<T> = Point2::new(T::ZERO, T::MINUS_ONE);
+    pub const DOWN: Point2<T> = Point2::new(T::ZERO, T::ONE);
+    pub const LEFT: Point2<T> = Point2::new(T::MINUS_ONE, T::ZERO);
+    pub const RIGHT: Point2<T> = Point2::new(T::ONE, T::ZERO);
+
+    /// Up, left, right, down.
+    pub const ORTHOGONAL: [Point2<T>; 4] = [Self::UP, Self::LEFT, Self::RIGHT, Self::DOWN];
+
+    /// The orthogonal directions plus the diagonals, in reading order.
+    pub const ALL_DIRECTIONS: [Point2<T>; 8] = [
+        Point2::new(T::MINUS_ONE, T::MINUS_ONE),
+        Self::UP,
+        Point2::new(T::ONE, T::MINUS_ONE),
+        Self::LEFT,
+        Self::RIGHT,
+        Point2::new(T::MINUS_ONE, T::ONE),
+        Self::DOWN,
+        Point2::new(T::ONE, T::ONE),
+    ];
+
+    /// Each coordinate replaced with -1, 0 or 1. For a diagonal or straight line this is the
+    /// step between points on it.
+    pub fn signum(self) -> Point2<T> {
+        Point2::new(signum(self.x), signum(self.y))
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Point2<T>> {
+        Self::ORTHOGONAL.into_iter().map(move |d| self + d)
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Point2<T>> {
+        Self::ALL_DIRECTIONS.into_iter().map(move |d| self + d)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Point2<T>;
+
+    fn add(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Point2<T>;
+
+    fn sub(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2<T> {
+    type Output = Point2<T>;
+
+    fn mul(self, scale: T) -> Point2<T> {
+        Point2::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Point2<T>;
+
+    fn neg(self) -> Point2<T> {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2<T> {
+    fn add_assign(&mut self, other: Point2<T>) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, other: Point2<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T> FromStr for Point2<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Point2<T>> {
+        let mut fields = parse_str(s, 2)?.into_iter();
+        Ok(Point2::new(fields.next().unwrap(), fields.next().unwrap()))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+
+    /// Converts each coordinate to another integer type, if they all fit.
+    pub fn try_cast<U>(self) -> Option<Point3<U>>
+    where
+        U: TryFrom<T>,
+    {
+        Some(Point3::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+            U::try_from(self.z).ok()?,
+        ))
+    }
+
+    /// Parses `field`, a slice of `line` in the form 'x,y,z'.
+    pub fn from_input(line: &InputLine, field: &str) -> Result<Point3<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let mut fields = parse_fields(line, field, 3)?.into_iter();
+        Ok(Point3::new(
+            fields.next().unwrap(),
+            fields.next().unwrap(),
+            fields.next().unwrap(),
+        ))
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    pub const ORIGIN: Point3<T> = Point3::new(T::ZERO, T::ZERO, T::ZERO);
+
+    pub fn manhattan_distance(self, other: Point3<T>) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y) + distance(self.z, other.z)
+    }
+
+    pub fn chebyshev_distance(self, other: Point3<T>) -> T {
+        distance(self.x, other.x)
+            .max(distance(self.y, other.y))
+            .max(distance(self.z, other.z))
+    }
+}
+
+impl<T: SignedCoordinate> Point3<T> {
+    /// One step along each axis in each direction.
+    pub const ORTHOGONAL: [Point3<T>; 6] = [
+        Point3::new(T::MINUS_ONE, T::ZERO, T::ZERO),
+        Point3::new(T::ONE, T::ZERO, T::ZERO),
+        Point3::new(T::ZERO, T::MINUS_ONE, T::ZERO),
+        Point3::new(T::ZERO, T::ONE, T::ZERO),
+        Point3::new(T::ZERO, T::ZERO, T::MINUS_ONE),
+        Point3::new(T::ZERO, T::ZERO, T::ONE),
+    ];
+
+    pub fn signum(self) -> Point3<T> {
+        Point3::new(signum(self.x), signum(self.y), signum(self.z))
+    }
+
+    pub fn neighbors6(self) -> impl Iterator<Item = Point3<T>> {
+        Self::ORTHOGONAL.into_iter().map(move |d| self + d)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, scale: T) -> Point3<T> {
+        Point3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Point3<T>;
+
+    fn neg(self) -> Point3<T> {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Point3<T>) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Point3<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+impl<T> FromStr for Point3<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Point3<T>> {
+        let mut fields = parse_str(s, 3)?.into_iter();
+        Ok(Point3::new(
+            fields.next().unwrap(),
+            fields.next().unwrap(),
+            fields.next().unwrap(),
+        ))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
@@ -0,0 +1,215 @@
+use crate::common::point::Position;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Brackets around the cells on the path in an ASCII render, with spaces around the others to
+/// keep the columns lined up. Plain text rather than colour, so renders can be opened in an editor
+/// and diffed, and the values along the path can still be read.
+const PATH_MARKERS: (char, char) = ('[', ']');
+
+/// Size in pixels of one grid cell in an SVG render.
+const SVG_CELL_SIZE: usize = 8;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RenderFormat {
+    /// The grid as text, with the cells on the path in brackets, e.g. `[7]`
+    Ascii,
+    /// A binary PPM image with one pixel per cell
+    Ppm,
+    Svg,
+}
+
+impl RenderFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RenderFormat::Ascii => "txt",
+            RenderFormat::Ppm => "ppm",
+            RenderFormat::Svg => "svg",
+        }
+    }
+}
+
+impl FromStr for RenderFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<RenderFormat> {
+        match s {
+            "ascii" => Ok(RenderFormat::Ascii),
+            "ppm" => Ok(RenderFormat::Ppm),
+            "svg" => Ok(RenderFormat::Svg),
+            _ => Err(anyhow::Error::msg(format!(
+                "Unrecognized render format {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Where and how to write renders.
+#[derive(Debug, Clone)]
+pub struct Render {
+    pub format: RenderFormat,
+    pub dir: String,
+}
+
+impl Render {
+    pub fn new(format: RenderFormat, dir: &str) -> Render {
+        Render {
+            format,
+            dir: dir.to_owned(),
+        }
+    }
+
+    /// Writes a `width` x `height` grid with `path` highlighted to `<dir>/<name>.<extension>`.
+    /// `cell` gives the value at each position, so the grid doesn't need to be stored, and
+    /// `brightness` maps each value to a grey level for the image formats.
+    pub fn write_path<T, C, F>(
+        &self,
+        name: &str,
+        (width, height): (usize, usize),
+        cell: C,
+        path: &[Position],
+        brightness: F,
+    ) -> Result<PathBuf>
+    where
+        T: fmt::Display,
+        C: Fn(Position) -> T,
+        F: Fn(&T) -> u8,
+    {
+        let contents = render_path((width, height), cell, path, self.format, brightness);
+        let file = Path::new(&self.dir).join(format!("{}.{}", name, self.format.extension()));
+        fs::create_dir_all(&self.dir).with_context(|| format!("Unable to create {}", self.dir))?;
+        fs::write(&file, contents)
+            .with_context(|| format!("Unable to write {}", file.display()))?;
+        Ok(file)
+    }
+}
+
+/// Renders a `width` x `height` grid, with the value at each position given by `cell`, with the
+/// cells on `path` highlighted.
+pub fn render_path<T, C, F>(
+    (width, height): (usize, usize),
+    cell: C,
+    path: &[Position],
+    format: RenderFormat,
+    brightness: F,
+) -> Vec<u8>
+where
+    T: fmt::Display,
+    C: Fn(Position) -> T,
+    F: Fn(&T) -> u8,
+{
+    let cells = Cells {
+        width,
+        height,
+        cell,
+    };
+    let on_path = path.iter().copied().collect::<HashSet<Position>>();
+    match format {
+        RenderFormat::Ascii => ascii(&cells, &on_path).into_bytes(),
+        RenderFormat::Ppm => ppm(&cells, &on_path, brightness),
+        RenderFormat::Svg => svg(&cells, path, brightness).into_bytes(),
+    }
+}
+
+/// A grid whose values are worked out as they're rendered.
+struct Cells<C> {
+    width: usize,
+    height: usize,
+    cell: C,
+}
+
+impl<C> Cells<C> {
+    /// Every position with its value, in reading order.
+    fn iter<T>(&self) -> impl Iterator<Item = (Position, T)> + '_
+    where
+        C: Fn(Position) -> T,
+    {
+        let width = self.width;
+        (0..self.height)
+            .flat_map(move |y| (0..width).map(move |x| Position::new(x, y)))
+            .map(|p| (p, (self.cell)(p)))
+    }
+}
+
+fn ascii<T, C>(cells: &Cells<C>, on_path: &HashSet<Position>) -> String
+where
+    T: fmt::Display,
+    C: Fn(Position) -> T,
+{
+    let mut s = String::new();
+    for (p, value) in cells.iter() {
+        if on_path.contains(&p) {
+            write!(s, "{}{}{}", PATH_MARKERS.0, value, PATH_MARKERS.1).unwrap();
+        } else {
+            write!(s, " {} ", value).unwrap();
+        }
+        if p.x + 1 == cells.width {
+            s.push('\n');
+        }
+    }
+    s
+}
+
+fn ppm<T, C, F>(cells: &Cells<C>, on_path: &HashSet<Position>, brightness: F) -> Vec<u8>
+where
+    C: Fn(Position) -> T,
+    F: Fn(&T) -> u8,
+{
+    let mut bytes = format!("P6\n{} {}\n255\n", cells.width, cells.height).into_bytes();
+    for (p, value) in cells.iter() {
+        if on_path.contains(&p) {
+            bytes.extend([255, 0, 0]);
+        } else {
+            let level = brightness(&value);
+            bytes.extend([level, level, level]);
+        }
+    }
+    bytes
+}
+
+fn svg<T, C, F>(cells: &Cells<C>, path: &[Position], brightness: F) -> String
+where
+    C: Fn(Position) -> T,
+    F: Fn(&T) -> u8,
+{
+    let mut s = String::new();
+    writeln!(
+        s,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+        cells.width * SVG_CELL_SIZE,
+        cells.height * SVG_CELL_SIZE,
+        cells.width,
+        cells.height
+    )
+    .unwrap();
+    for (p, value) in cells.iter() {
+        let level = brightness(&value);
+        writeln!(
+            s,
+            r#"<rect x="{}" y="{}" width="1" height="1" fill="rgb({},{},{})"/>"#,
+            p.x, p.y, level, level, level
+        )
+        .unwrap();
+    }
+
+    // through the middle of each cell
+    let points = path
+        .iter()
+        .map(|p| format!("{}.5,{}.5", p.x, p.y))
+        .collect::<Vec<String>>()
+        .join(" ");
+    writeln!(
+        s,
+        r#"<polyline points="{}" fill="none" stroke="red" stroke-width="0.4" stroke-linejoin="round"/>"#,
+        points
+    )
+    .unwrap();
+    s.push_str("</svg>\n");
+    s
+}
@@ -0,0 +1,300 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A path found by one of the searches, from the start state to the goal inclusive.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SearchResult<S, C> {
+    /// Sum of the costs of every step along `path`
+    pub cost: C,
+    pub path: Vec<S>,
+}
+
+/// Every state the search has seen, with the state it was reached from on the cheapest path so far.
+struct Visited<S, C> {
+    indices: HashMap<S, usize>,
+    /// (state, cost to reach it, index of the previous state)
+    nodes: Vec<(S, C, Option<usize>)>,
+}
+
+impl<S, C> Visited<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy,
+{
+    fn new() -> Visited<S, C> {
+        Visited {
+            indices: HashMap::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, state: S, cost: C, previous: Option<usize>) -> usize {
+        let index = self.nodes.len();
+        self.indices.insert(state.clone(), index);
+        self.nodes.push((state, cost, previous));
+        index
+    }
+
+    fn path_to(&self, index: usize) -> SearchResult<S, C> {
+        let cost = self.nodes[index].1;
+        let mut path = Vec::new();
+        let mut current = Some(index);
+        while let Some(i) = current {
+            path.push(self.nodes[i].0.clone());
+            current = self.nodes[i].2;
+        }
+        path.reverse();
+        SearchResult { cost, path }
+    }
+}
+
+/// Breadth first search. Finds the path to a goal with the fewest steps, ignoring step costs when
+/// choosing it.
+pub fn bfs<S, C, N, I, G>(start: S, mut neighbors: N, mut is_goal: G) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Default + Add<Output = C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(visited.insert(start, C::default(), None));
+
+    while let Some(index) = queue.pop_front() {
+        let (state, cost, _) = visited.nodes[index].clone();
+        if is_goal(&state) {
+            return Some(visited.path_to(index));
+        }
+        for (next, step) in neighbors(&state) {
+            if !visited.indices.contains_key(&next) {
+                queue.push_back(visited.insert(next, cost + step, Some(index)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Depth first search. Finds some path to a goal, not necessarily the cheapest or shortest.
+pub fn dfs<S, C, N, I, G>(start: S, mut neighbors: N, mut is_goal: G) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Default + Add<Output = C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    let mut visited = Visited::new();
+    let mut stack = vec![visited.insert(start, C::default(), None)];
+
+    while let Some(index) = stack.pop() {
+        let (state, cost, _) = visited.nodes[index].clone();
+        if is_goal(&state) {
+            return Some(visited.path_to(index));
+        }
+        for (next, step) in neighbors(&state) {
+            if !visited.indices.contains_key(&next) {
+                stack.push(visited.insert(next, cost + step, Some(index)));
+            }
+        }
+    }
+
+    None
+}
+
+/// The cheapest path to a goal.
+pub fn dijkstra<S, C, N, I, G>(start: S, neighbors: N, is_goal: G) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(start, neighbors, is_goal, |_| C::default())
+}
+
+/// The cheapest path to a goal, exploring states in order of their cost so far plus `heuristic`.
+/// The heuristic must never overestimate the remaining cost, or the path found may not be the
+/// cheapest. It doesn't have to be consistent: a state that has already been explored is explored
+/// again if a cheaper path to it turns up, which a consistent heuristic never causes.
+pub fn astar<S, C, N, I, G, H>(
+    start: S,
+    mut neighbors: N,
+    mut is_goal: G,
+    mut heuristic: H,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+    H: FnMut(&S) -> C,
+{
+    let mut visited = Visited::new();
+    let mut heap = BinaryHeap::new();
+    let start_estimate = heuristic(&start);
+    heap.push(Reverse((
+        start_estimate,
+        C::default(),
+        visited.insert(start, C::default(), None),
+    )));
+
+    while let Some(Reverse((_, pushed_cost, index))) = heap.pop() {
+        // a state is pushed again each time a cheaper path to it is found, so skip the entries
+        // for the paths that have since been beaten
+        let (state, cost, _) = visited.nodes[index].clone();
+        if pushed_cost != cost {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(visited.path_to(index));
+        }
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            match visited.indices.get(&next).copied() {
+                Some(i) if visited.nodes[i].1 <= next_cost => {}
+                Some(i) => {
+                    visited.nodes[i].1 = next_cost;
+                    visited.nodes[i].2 = Some(index);
+                    heap.push(Reverse((next_cost + heuristic(&next), next_cost, i)));
+                }
+                None => {
+                    let estimate = heuristic(&next);
+                    let i = visited.insert(next, next_cost, Some(index));
+                    heap.push(Reverse((next_cost + estimate, next_cost, i)));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra over states numbered `0..num_states`, keeping costs and predecessors in flat arrays
+/// instead of hash maps. Much faster and smaller when nearly every state gets visited, e.g. every
+/// cell of a grid.
+pub fn dijkstra_dense<C, N, I, G>(
+    num_states: usize,
+    start: usize,
+    mut neighbors: N,
+    mut is_goal: G,
+) -> Option<SearchResult<usize, C>>
+where
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: FnMut(usize) -> I,
+    I: IntoIterator<Item = (usize, C)>,
+    G: FnMut(usize) -> bool,
+{
+    const UNREACHED: usize = usize::MAX;
+
+    let mut costs = vec![C::default(); num_states];
+    let mut previous = vec![UNREACHED; num_states];
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((C::default(), start)));
+
+    while let Some(Reverse((cost, state))) = heap.pop() {
+        // a state can be pushed several times as cheaper paths to it are found, so skip the stale
+        // entries
+        if cost > costs[state] {
+            continue;
+        }
+        if is_goal(state) {
+            let mut path = vec![state];
+            let mut current = state;
+            while current != start {
+                current = previous[current];
+                path.push(current);
+            }
+            path.reverse();
+            return Some(SearchResult { cost, path });
+        }
+        for (next, step) in neighbors(state) {
+            let next_cost = cost + step;
+            let reached = next == start || previous[next] != UNREACHED;
+            if !reached || next_cost < costs[next] {
+                costs[next] = next_cost;
+                previous[next] = state;
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Every state reachable from `start`, including `start`, in breadth first order.
+pub fn reachable<S, C, N, I>(start: S, mut neighbors: N) -> Vec<S>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut seen = HashSet::new();
+    let mut order = Vec::new();
+    let mut queue = VecDeque::new();
+    seen.insert(start.clone());
+    queue.push_back(start);
+
+    while let Some(state) = queue.pop_front() {
+        for (next, _) in neighbors(&state) {
+            if seen.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+        order.push(state);
+    }
+
+    order
+}
+
+/// Walks every path from `start` to a goal, calling `on_path` with each one, and returns how many
+/// there were. States are not de-duplicated, so the neighbours must eventually run out along every
+/// branch, e.g. by carrying what has been visited so far in the state.
+pub fn all_paths<S, C, N, I, G, P>(
+    start: S,
+    mut neighbors: N,
+    mut is_goal: G,
+    mut on_path: P,
+) -> usize
+where
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+    P: FnMut(&[S]),
+{
+    let mut path = Vec::new();
+    // the neighbours of each state on the current path that are still to be walked
+    let mut pending: Vec<Vec<S>> = vec![vec![start]];
+    let mut count = 0;
+
+    while let Some(siblings) = pending.last_mut() {
+        let state = match siblings.pop() {
+            Some(state) => state,
+            None => {
+                pending.pop();
+                path.pop();
+                continue;
+            }
+        };
+
+        if is_goal(&state) {
+            path.push(state);
+            on_path(&path);
+            path.pop();
+            count += 1;
+            continue;
+        }
+
+        let next = neighbors(&state).into_iter().map(|(s, _)| s).collect();
+        path.push(state);
+        pending.push(next);
+    }
+
+    count
+}
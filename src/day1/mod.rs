@@ -1,31 +1,50 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
-use std::io::BufRead;
 
-pub fn day1(args: &Arguments) -> Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
+const EXAMPLE: &str = "\
+199
+200
+208
+210
+200
+207
+240
+269
+260
+263
+";
 
-    let mut numbers: Vec<i64> = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        let num = line.parse()?;
-        numbers.push(num);
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = Vec<i64>;
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let mut numbers: Vec<i64> = Vec::new();
+        for line in input.lines() {
+            let line = line?;
+            let num = line.parse(line.text.trim())?;
+            numbers.push(num);
+        }
+
+        Ok(numbers)
     }
-    let numbers = numbers;
 
-    let result = if args.part == 1 {
-        count_increasing_windows(&numbers, 2)
-    } else {
-        count_increasing_windows(&numbers, 4)
-    }?;
+    fn part1(&self, numbers: Self::Input) -> Result<Answer> {
+        count_increasing_windows(&numbers, 2).map(Answer::from)
+    }
 
-    info!("{:?}", result);
+    fn part2(&self, numbers: Self::Input) -> Result<Answer> {
+        count_increasing_windows(&numbers, 4).map(Answer::from)
+    }
 
-    Ok(())
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 7usize, 5usize)]
+    }
 }
 
-fn count_increasing_windows(numbers: &Vec<i64>, window_size: usize) -> Result<usize> {
+fn count_increasing_windows(numbers: &[i64], window_size: usize) -> Result<usize> {
     let count = numbers
         .windows(window_size)
         .filter(|window| window[0] < window[window_size - 1])
@@ -1,29 +1,54 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
 use itertools::Itertools;
-use std::io::BufRead;
 
-pub fn day10(args: &Arguments) -> Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
+const EXAMPLE: &str = "\
+[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]
+";
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Vec<Vec<char>>;
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let mut lines = Vec::new();
+        for line in input.lines() {
+            let line = line?;
+            for (i, c) in line.text.chars().enumerate() {
+                if !is_open_char(c) && !is_close_char(c) {
+                    return Err(line
+                        .error_at(i + 1, format!("Expected a bracket but got '{}'", c))
+                        .into());
+                }
+            }
+            lines.push(line.text.chars().collect());
+        }
 
-    let mut lines = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        let line = line.chars().collect();
-        lines.push(line);
+        Ok(lines)
     }
-    let lines = lines;
 
-    let result = if args.part == 1 {
-        part1(lines)
-    } else {
-        part2(lines)
-    }?;
+    fn part1(&self, lines: Self::Input) -> Result<Answer> {
+        part1(lines).map(Answer::from)
+    }
 
-    info!("{:?}", result);
+    fn part2(&self, lines: Self::Input) -> Result<Answer> {
+        part2(lines).map(Answer::from)
+    }
 
-    Ok(())
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 26397u64, 288957u64)]
+    }
 }
 
 #[derive(Debug)]
@@ -34,23 +59,11 @@ enum SyntaxError {
 }
 
 fn is_open_char(c: char) -> bool {
-    match c {
-        '(' => true,
-        '[' => true,
-        '{' => true,
-        '<' => true,
-        _ => false,
-    }
+    matches!(c, '(' | '[' | '{' | '<')
 }
 
 fn is_close_char(c: char) -> bool {
-    match c {
-        ')' => true,
-        ']' => true,
-        '}' => true,
-        '>' => true,
-        _ => false,
-    }
+    matches!(c, ')' | ']' | '}' | '>')
 }
 
 fn open_char_for_close_char(close_char: char) -> char {
@@ -72,8 +85,7 @@ fn find_syntax_errors(line: &[char]) -> SyntaxError {
             continue;
         }
         if is_close_char(*c) {
-            let last = s.pop().unwrap();
-            if last == open_char_for_close_char(*c) {
+            if s.pop() == Some(open_char_for_close_char(*c)) {
                 continue;
             } else {
                 return SyntaxError::IllegalCharacter(*c);
@@ -101,12 +113,12 @@ fn value_of_illegal_char(c: char) -> u64 {
 fn part1(lines: Vec<Vec<char>>) -> Result<u64> {
     let result = lines
         .iter()
-        .map(|line| find_syntax_errors(&line))
+        .map(|line| find_syntax_errors(line))
         .map(|e| match e {
             SyntaxError::IllegalCharacter(c) => value_of_illegal_char(c),
             _ => 0,
         })
-        .fold(0, |accum, value| accum + value);
+        .sum();
 
     Ok(result)
 }
@@ -132,7 +144,7 @@ fn value_of_incomplete_line(stack: &[char]) -> u64 {
 fn part2(lines: Vec<Vec<char>>) -> Result<u64> {
     let line_values = lines
         .iter()
-        .map(|line| find_syntax_errors(&line))
+        .map(|line| find_syntax_errors(line))
         .map(|e| match e {
             SyntaxError::IncompleteLine(stack) => value_of_incomplete_line(&stack),
             _ => 0,
@@ -1,35 +1,46 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::common::grid::Grid;
+use crate::common::point::Position;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
-use std::io::BufRead;
-
-pub fn day11(args: &Arguments) -> Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
-
-    let mut lines = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        let line = line
-            .chars()
-            .map(|c| Octopus::new(String::from(c).parse().unwrap()))
-            .collect::<Vec<Octopus>>();
-        lines.push(line);
+
+const EXAMPLE: &str = "\
+5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526
+";
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = Grid<Octopus>;
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        Ok(Grid::from_digit_lines(input)?.map(Octopus::new))
     }
-    let lines = lines;
 
-    let result = if args.part == 1 {
-        part1(lines)
-    } else {
-        part2(lines)
-    }?;
+    fn part1(&self, octopods: Self::Input) -> Result<Answer> {
+        part1(octopods).map(Answer::from)
+    }
 
-    info!("{:?}", result);
+    fn part2(&self, octopods: Self::Input) -> Result<Answer> {
+        part2(octopods).map(Answer::from)
+    }
 
-    Ok(())
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 1656usize, 195usize)]
+    }
 }
 
 #[derive(Debug, Default)]
-struct Octopus {
+pub struct Octopus {
     energy: u64,
     flashed: bool,
 }
@@ -43,66 +54,16 @@ impl Octopus {
     }
 }
 
-#[derive(Debug, Default)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-impl Position {
-    fn new(x: usize, y: usize) -> Position {
-        Position { x, y }
-    }
-}
-
-fn increase_energy(lines: &mut Vec<Vec<Octopus>>) {
-    for y in 0..lines.len() {
-        for x in 0..lines[y].len() {
-            lines[y][x].energy += 1;
-        }
-    }
-}
-
-fn adjacent_octopods(x: usize, y: usize, x_max: usize, y_max: usize) -> Vec<Position> {
-    let mut adjacent = Vec::new();
-
-    /*
-    x-1,y-1     x,y-1       x+1,y-1
-    x-1,y       ---         x+1,y
-    x-1,y+1     x,y+1       x+1,y+1
-    */
-    if x != 0 && y != 0 {
-        adjacent.push(Position::new(x - 1, y - 1));
-    }
-    if y != 0 {
-        adjacent.push(Position::new(x, y - 1));
-    }
-    if x + 1 < x_max && y != 0 {
-        adjacent.push(Position::new(x + 1, y - 1));
-    }
-    if x != 0 {
-        adjacent.push(Position::new(x - 1, y));
-    }
-    if x + 1 < x_max {
-        adjacent.push(Position::new(x + 1, y));
-    }
-    if x != 0 && y + 1 < y_max {
-        adjacent.push(Position::new(x - 1, y + 1));
+fn increase_energy(octopods: &mut Grid<Octopus>) {
+    for octopus in octopods.values_mut() {
+        octopus.energy += 1;
     }
-    if y + 1 < y_max {
-        adjacent.push(Position::new(x, y + 1));
-    }
-    if x + 1 < x_max && y + 1 < y_max {
-        adjacent.push(Position::new(x + 1, y + 1));
-    }
-
-    adjacent
 }
 
 const FLASH_THRESHOLD: u64 = 10;
 
-fn flash_octopus(lines: &mut Vec<Vec<Octopus>>, p: Position) {
-    let mut octopus = lines[p.y].get_mut(p.x).unwrap();
+fn flash_octopus(octopods: &mut Grid<Octopus>, p: Position) {
+    let octopus = &mut octopods[p];
 
     if octopus.energy < FLASH_THRESHOLD {
         return;
@@ -114,63 +75,57 @@ fn flash_octopus(lines: &mut Vec<Vec<Octopus>>, p: Position) {
         octopus.flashed = true;
     }
 
-    let adjacent = adjacent_octopods(p.x, p.y, lines[p.y].len(), lines.len());
+    let adjacent = octopods.neighbors8(p).collect::<Vec<Position>>();
     for adjacent_p in adjacent {
-        let mut adjacent_o = lines[adjacent_p.y].get_mut(adjacent_p.x).unwrap();
-        adjacent_o.energy += 1;
-        flash_octopus(lines, adjacent_p);
+        octopods[adjacent_p].energy += 1;
+        flash_octopus(octopods, adjacent_p);
     }
 }
 
-fn flash(lines: &mut Vec<Vec<Octopus>>) {
-    for y in 0..lines.len() {
-        for x in 0..lines[y].len() {
-            flash_octopus(lines, Position::new(x, y));
-        }
+fn flash(octopods: &mut Grid<Octopus>) {
+    let positions = octopods.positions().collect::<Vec<Position>>();
+    for p in positions {
+        flash_octopus(octopods, p);
     }
 }
 
-fn find_flashed(lines: &mut Vec<Vec<Octopus>>) -> Vec<Position> {
-    let mut flashed = Vec::new();
-    for y in 0..lines.len() {
-        for x in 0..lines[y].len() {
-            if lines[y][x].flashed {
-                flashed.push(Position::new(x, y));
-            }
-        }
-    }
-    flashed
+fn find_flashed(octopods: &Grid<Octopus>) -> Vec<Position> {
+    octopods
+        .iter()
+        .filter(|(_, octopus)| octopus.flashed)
+        .map(|(p, _)| p)
+        .collect()
 }
 
-fn reset_flashed(lines: &mut Vec<Vec<Octopus>>, flashed: &[Position]) {
+fn reset_flashed(octopods: &mut Grid<Octopus>, flashed: &[Position]) {
     for p in flashed {
-        let mut o = lines[p.y].get_mut(p.x).unwrap();
+        let o = &mut octopods[*p];
         o.energy = 0;
         o.flashed = false;
     }
 }
 
-fn step(lines: &mut Vec<Vec<Octopus>>) -> Vec<Position> {
-    increase_energy(lines);
-    flash(lines);
-    let flashed = find_flashed(lines);
-    reset_flashed(lines, &flashed);
+fn step(octopods: &mut Grid<Octopus>) -> Vec<Position> {
+    increase_energy(octopods);
+    flash(octopods);
+    let flashed = find_flashed(octopods);
+    reset_flashed(octopods, &flashed);
     flashed
 }
 
-fn part1(mut lines: Vec<Vec<Octopus>>) -> Result<usize> {
+fn part1(mut octopods: Grid<Octopus>) -> Result<usize> {
     let mut count = 0;
     for _ in 0..100 {
-        let flashed = step(&mut lines);
+        let flashed = step(&mut octopods);
         count += flashed.len();
     }
     Ok(count)
 }
 
-fn part2(mut lines: Vec<Vec<Octopus>>) -> Result<usize> {
-    let num_octopods = lines.iter().flat_map(|line| line.iter()).count();
+fn part2(mut octopods: Grid<Octopus>) -> Result<usize> {
+    let num_octopods = octopods.len();
     for i in 0..1000 {
-        let flashed = step(&mut lines);
+        let flashed = step(&mut octopods);
         if flashed.len() == num_octopods {
             return Ok(i + 1);
         }
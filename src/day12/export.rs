@@ -0,0 +1,154 @@
+use super::Graph;
+use anyhow::{Context, Result};
+use serde_derive::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where to write the cave system, and whether to walk every path to count how often each tunnel
+/// is used.
+#[derive(Debug, Clone)]
+pub struct Export {
+    pub dir: String,
+    pub edge_usage: bool,
+}
+
+impl Export {
+    pub fn new(dir: &str, edge_usage: bool) -> Export {
+        Export {
+            dir: dir.to_owned(),
+            edge_usage,
+        }
+    }
+
+    /// Writes `<dir>/<name>.dot` and `<dir>/<name>.json`, returning their paths. `usage` maps each
+    /// tunnel, as a pair of cave ids with the smaller first, to the number of paths through it.
+    pub fn write(
+        &self,
+        name: &str,
+        graph: &Graph,
+        usage: Option<&HashMap<(usize, usize), usize>>,
+    ) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(&self.dir).with_context(|| format!("Unable to create {}", self.dir))?;
+
+        let contents = [
+            ("dot", dot(graph, usage)),
+            (
+                "json",
+                serde_json::to_string_pretty(&adjacency(graph, usage))?,
+            ),
+        ];
+        let mut files = Vec::new();
+        for (extension, contents) in contents {
+            let file = Path::new(&self.dir).join(format!("{}.{}", name, extension));
+            fs::write(&file, contents)
+                .with_context(|| format!("Unable to write {}", file.display()))?;
+            files.push(file);
+        }
+        Ok(files)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Start,
+    End,
+    Big,
+    Small,
+}
+
+fn kind(graph: &Graph, cave: usize) -> Kind {
+    match graph.names[cave].as_str() {
+        "start" => Kind::Start,
+        "end" => Kind::End,
+        _ if graph.is_big(cave) => Kind::Big,
+        _ => Kind::Small,
+    }
+}
+
+/// Every tunnel once, as a pair of cave ids with the smaller first.
+fn edges(graph: &Graph) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    for (cave, neighbors) in graph.neighbors.iter().enumerate() {
+        for neighbor in neighbors {
+            if cave <= *neighbor {
+                edges.push((cave, *neighbor));
+            }
+        }
+    }
+    edges
+}
+
+fn dot(graph: &Graph, usage: Option<&HashMap<(usize, usize), usize>>) -> String {
+    let mut s = String::from("graph caves {\n");
+    for (cave, name) in graph.names.iter().enumerate() {
+        let style = match kind(graph, cave) {
+            Kind::Start => "shape=doublecircle, style=filled, fillcolor=palegreen",
+            Kind::End => "shape=doublecircle, style=filled, fillcolor=salmon",
+            Kind::Big => "shape=box, style=filled, fillcolor=lightblue",
+            Kind::Small => "shape=ellipse",
+        };
+        writeln!(s, "    \"{}\" [{}];", name, style).unwrap();
+    }
+    for (a, b) in edges(graph) {
+        write!(s, "    \"{}\" -- \"{}\"", graph.names[a], graph.names[b]).unwrap();
+        if let Some(usage) = usage {
+            write!(s, " [label=\"{}\"]", usage.get(&(a, b)).unwrap_or(&0)).unwrap();
+        }
+        s.push_str(";\n");
+    }
+    s.push_str("}\n");
+    s
+}
+
+#[derive(Debug, Serialize)]
+struct Adjacency<'a> {
+    caves: BTreeMap<&'a str, CaveEntry<'a>>,
+    edges: Vec<EdgeEntry<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CaveEntry<'a> {
+    kind: Kind,
+    neighbors: Vec<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct EdgeEntry<'a> {
+    caves: [&'a str; 2],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paths: Option<usize>,
+}
+
+fn adjacency<'a>(
+    graph: &'a Graph,
+    usage: Option<&HashMap<(usize, usize), usize>>,
+) -> Adjacency<'a> {
+    let caves = graph
+        .names
+        .iter()
+        .enumerate()
+        .map(|(cave, name)| {
+            let mut neighbors = graph.neighbors[cave]
+                .iter()
+                .map(|neighbor| graph.names[*neighbor].as_str())
+                .collect::<Vec<&str>>();
+            neighbors.sort_unstable();
+            let entry = CaveEntry {
+                kind: kind(graph, cave),
+                neighbors,
+            };
+            (name.as_str(), entry)
+        })
+        .collect();
+    let edges = edges(graph)
+        .into_iter()
+        .map(|(a, b)| EdgeEntry {
+            caves: [graph.names[a].as_str(), graph.names[b].as_str()],
+            paths: usage.map(|usage| *usage.get(&(a, b)).unwrap_or(&0)),
+        })
+        .collect();
+    Adjacency { caves, edges }
+}
@@ -1,181 +1,421 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::common::parse::{InputLine, ParseError};
+use crate::common::search::all_paths;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Part, Solver};
 use anyhow::Result;
+use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
-use std::io::BufRead;
 
-pub fn day12(args: &Arguments) -> Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
+mod export;
+mod policy;
 
-    let mut graph = Graph::default();
-    for line in reader.lines() {
-        let line = line?;
-        let line = line;
-        graph.add_edge(line);
-    }
-    let graph = graph;
+pub use export::Export;
+pub use policy::RevisitPolicy;
+use policy::Rules;
 
-    let result = if args.part == 1 {
-        part1(graph)
-    } else {
-        part2(graph)
-    }?;
+const SMALL_EXAMPLE: &str = "\
+start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end
+";
 
-    info!("{:?}", result);
+const MEDIUM_EXAMPLE: &str = "\
+dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc
+";
 
-    Ok(())
+const LARGE_EXAMPLE: &str = "\
+fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW
+";
+
+#[derive(Debug, Default)]
+pub struct Day12 {
+    list_paths: bool,
+    policy: Option<RevisitPolicy>,
+    export: Option<Export>,
 }
 
-#[derive(Debug, Default, Eq, PartialEq, Hash, Clone)]
-struct Cave {
-    name: String,
-    is_big: bool,
+impl Day12 {
+    /// `list_paths` walks and logs every path instead of just counting them, which is much slower.
+    /// `policy` replaces the rules for both parts, and `export` also writes out the cave system for
+    /// each part.
+    pub fn new(list_paths: bool, policy: Option<RevisitPolicy>, export: Option<Export>) -> Day12 {
+        Day12 {
+            list_paths,
+            policy,
+            export,
+        }
+    }
+
+    fn solve(&self, part: Part, graph: &Graph, policy: RevisitPolicy) -> Result<usize> {
+        let policy = self.policy.clone().unwrap_or(policy);
+        let rules = policy.rules(graph)?;
+        let edge_usage = self.export.as_ref().is_some_and(|export| export.edge_usage);
+
+        let mut usage = HashMap::new();
+        let count = if self.list_paths || edge_usage {
+            walk_paths(graph, &rules, |path| {
+                if self.list_paths {
+                    let names = path.iter().map(|cave| graph.names[*cave].as_str());
+                    info!("{}", names.collect::<Vec<&str>>().join(","));
+                }
+                if edge_usage {
+                    // paths can go through a tunnel more than once, but only count once
+                    let tunnels = path
+                        .windows(2)
+                        .map(|tunnel| (tunnel[0].min(tunnel[1]), tunnel[0].max(tunnel[1])))
+                        .collect::<HashSet<(usize, usize)>>();
+                    for tunnel in tunnels {
+                        *usage.entry(tunnel).or_insert(0) += 1;
+                    }
+                }
+            })
+        } else {
+            count_paths(graph, &rules)
+        };
+
+        if let Some(export) = &self.export {
+            let name = format!("day12-part{}", part);
+            let files = export.write(&name, graph, edge_usage.then_some(&usage))?;
+            for file in files {
+                info!("Wrote the part {} cave system to {}", part, file.display());
+            }
+        }
+
+        Ok(count)
+    }
 }
 
-impl Cave {
-    fn new(name: String) -> Cave {
-        let is_big = name.to_uppercase() == name;
-        Cave { name, is_big }
+impl Solver for Day12 {
+    type Input = Graph;
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let name = input.name().to_owned();
+        let mut graph = Graph::default();
+        for line in input.lines() {
+            graph.add_edge(&line?)?;
+        }
+
+        for cave in ["start", "end"] {
+            if !graph.ids.contains_key(cave) {
+                return Err(anyhow::Error::msg(format!(
+                    "{}: there's no '{}' cave",
+                    name, cave
+                )));
+            }
+        }
+
+        Ok(graph)
+    }
+
+    fn part1(&self, graph: Self::Input) -> Result<Answer> {
+        self.solve(Part::One, &graph, RevisitPolicy::part1())
+            .map(Answer::from)
+    }
+
+    fn part2(&self, graph: Self::Input) -> Result<Answer> {
+        self.solve(Part::Two, &graph, RevisitPolicy::part2())
+            .map(Answer::from)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::new(SMALL_EXAMPLE, 10usize, 36usize),
+            Example::new(MEDIUM_EXAMPLE, 19usize, 103usize),
+            Example::new(LARGE_EXAMPLE, 226usize, 3509usize),
+        ]
     }
 }
 
+/// The most small caves a graph can have, one per bit of a visited set.
+const MAX_SMALL_CAVES: usize = u64::BITS as usize;
+
+/// The caves and the tunnels between them. Caves are numbered in the order they first appear.
 #[derive(Debug, Default)]
-struct Graph {
-    g: HashMap<String, HashSet<String>>,
-    caves: HashMap<String, Cave>,
-    start: String,
-    end: String,
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    neighbors: Vec<Vec<usize>>,
+    /// The bit for each small cave in a visited set, or 0 for big caves
+    masks: Vec<u64>,
+    num_small: usize,
 }
 
-impl Graph {
-    fn add_edge(&mut self, edge: String) {
-        let mut split: Vec<String> = edge.split("-").map(|s| s.into()).collect();
-        let left = split.remove(0);
-        let right = split.remove(0);
-
-        {
-            self.caves
-                .entry(left.clone())
-                .or_insert(Cave::new(left.clone()));
-        }
-        {
-            self.caves
-                .entry(right.clone())
-                .or_insert(Cave::new(right.clone()));
-        }
+/// Big caves are named in uppercase.
+fn is_big_name(name: &str) -> bool {
+    name.to_uppercase() == name
+}
 
-        let node = self.g.entry(left.clone()).or_insert(Default::default());
-        node.insert(right.clone());
+fn check_name(line: &InputLine, name: &str) -> Result<(), ParseError> {
+    if name.is_empty() {
+        return Err(line.error(name, "Expected a cave name"));
+    }
+    if let Some((i, c)) = name.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        return Err(line.error(
+            &name[i..],
+            format!("Cave names can only have letters but got '{}'", c),
+        ));
+    }
+    if !is_big_name(name) && name.to_lowercase() != name {
+        return Err(line.error(
+            name,
+            "Cave names must be all uppercase (big) or all lowercase (small)",
+        ));
+    }
+    Ok(())
+}
 
-        let node = self.g.entry(right.clone()).or_insert(Default::default());
-        node.insert(left.clone());
+impl Graph {
+    fn add_edge(&mut self, line: &InputLine) -> Result<(), ParseError> {
+        let (left, right) = line.split_once(&line.text, "-")?;
+        check_name(line, left)?;
+        check_name(line, right)?;
 
-        if left == "start" {
-            self.start = left.clone();
-        } else if left == "end" {
-            self.end = left.clone();
+        if left == right {
+            return Err(line.error(
+                &line.text,
+                format!("Tunnel from '{}' leads back to itself", left),
+            ));
+        }
+        // paths could go back and forth between them forever
+        if is_big_name(left) && is_big_name(right) {
+            return Err(line.error(
+                &line.text,
+                format!(
+                    "Big caves '{}' and '{}' are connected, so there'd be infinitely many paths",
+                    left, right
+                ),
+            ));
         }
 
-        if right == "start" {
-            self.start = right.clone();
-        } else if right == "end" {
-            self.end = right.clone();
+        let left = self.intern(line, left)?;
+        let right = self.intern(line, right)?;
+        if self.neighbors[left].contains(&right) {
+            warn!(
+                "{}:{}: ignoring duplicate tunnel {}",
+                line.file, line.number, line.text
+            );
+            return Ok(());
         }
+        self.neighbors[left].push(right);
+        self.neighbors[right].push(left);
+
+        Ok(())
     }
-}
 
-fn can_visit_part1(_graph: &Graph, visit_counts: &HashMap<String, u64>, next: &Cave) -> bool {
-    next.is_big || !visit_counts.contains_key(&next.name)
-}
+    /// The id of the cave called `name`, adding it if it's new.
+    fn intern(&mut self, line: &InputLine, name: &str) -> Result<usize, ParseError> {
+        if let Some(id) = self.ids.get(name) {
+            return Ok(*id);
+        }
+
+        let mask = if is_big_name(name) {
+            0
+        } else if self.num_small == MAX_SMALL_CAVES {
+            return Err(line.error(
+                name,
+                format!("Can't have more than {} small caves", MAX_SMALL_CAVES),
+            ));
+        } else {
+            self.num_small += 1;
+            1 << (self.num_small - 1)
+        };
 
-fn can_visit_part2(graph: &Graph, visit_counts: &HashMap<String, u64>, next: &Cave) -> bool {
-    if next.is_big {
-        return true;
+        let id = self.names.len();
+        self.names.push(name.to_owned());
+        self.ids.insert(name.to_owned(), id);
+        self.neighbors.push(Vec::new());
+        self.masks.push(mask);
+        Ok(id)
     }
 
-    if next.name == graph.start {
-        return false;
+    fn id(&self, name: &str) -> Result<usize> {
+        self.ids
+            .get(name)
+            .copied()
+            .ok_or_else(|| anyhow::Error::msg(format!("There's no '{}' cave", name)))
     }
 
-    if !visit_counts.contains_key(&next.name) {
-        return true;
+    fn is_big(&self, cave: usize) -> bool {
+        self.masks[cave] == 0
     }
+}
 
-    for count in visit_counts {
-        if *count.1 > 1 {
-            return false;
+/// Where a path through the caves has got to so far.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+struct Visit {
+    cave: usize,
+    /// Small caves the path has already been through, as a set of `Graph::masks`
+    visited: u64,
+    /// Every visit to a small cave after the first, as sorted cave ids
+    revisited: Vec<usize>,
+}
+
+impl Visit {
+    fn start(graph: &Graph, start: usize) -> Visit {
+        Visit {
+            cave: start,
+            visited: graph.masks[start],
+            revisited: Vec::new(),
         }
     }
 
-    return true;
-}
+    fn has_visited(&self, graph: &Graph, cave: usize) -> bool {
+        self.visited & graph.masks[cave] != 0
+    }
 
-fn path_to_visit_counts(graph: &Graph, current_path: &Vec<String>) -> HashMap<String, u64> {
-    let mut counts = HashMap::new();
-    for name in current_path {
-        let cave = graph.caves.get(name).unwrap();
-        if cave.is_big {
-            continue;
+    fn next(&self, graph: &Graph, cave: usize) -> Visit {
+        let mut revisited = self.revisited.clone();
+        if self.has_visited(graph, cave) {
+            let i = revisited.partition_point(|c| *c < cave);
+            revisited.insert(i, cave);
         }
-        if !counts.contains_key(name) {
-            counts.insert(name.clone(), 0u64);
+        Visit {
+            cave,
+            visited: self.visited | graph.masks[cave],
+            revisited,
         }
-        counts.entry(name.clone()).and_modify(|x| *x += 1);
     }
-    counts
-}
 
-// could probably be more efficient by not cloning everywhere, and
-// by not calculating visit_counts every time, and by adding a cache
-// for each node, but this is good enough
-fn find_paths<F>(graph: &Graph, current_path: &Vec<String>, can_visit: &F) -> Vec<Vec<String>>
-where
-    F: Fn(&Graph, &HashMap<String, u64>, &Cave) -> bool,
-{
-    let start = current_path.last().unwrap();
-    if *start == graph.end {
-        let mut path = Vec::new();
-        path.push(start.clone());
-        let mut paths = Vec::new();
-        paths.push(path);
-        return paths;
-    }
-
-    let mut paths = Vec::new();
-    let visit_counts = path_to_visit_counts(graph, current_path);
-    for next in graph.g.get(start).unwrap() {
-        let cave = graph.caves.get(next).unwrap();
-        if !can_visit(graph, &visit_counts, cave) {
-            continue;
+    fn can_visit(&self, graph: &Graph, rules: &Rules, next: usize) -> bool {
+        if rules.forbidden[next] {
+            return false;
+        }
+        if graph.is_big(next) || !self.has_visited(graph, next) {
+            return true;
         }
 
-        let mut next_current = current_path.clone();
-        next_current.push(next.clone());
-        let next_current = next_current;
-        let sub_paths = find_paths(graph, &next_current, can_visit);
-
-        for sub_path in sub_paths {
-            let mut path = Vec::new();
-            path.push(start.clone());
-            sub_path.into_iter().for_each(|name| path.push(name));
-            paths.push(path);
+        let revisits = self.revisited.iter().filter(|c| **c == next).count();
+        if revisits >= rules.revisits[next] {
+            return false;
         }
+        revisits > 0 || self.revisited.iter().dedup().count() < rules.max_revisited_caves
+    }
+
+    /// Every visit that can follow this one.
+    fn neighbors<'a>(
+        &'a self,
+        graph: &'a Graph,
+        rules: &'a Rules,
+    ) -> impl Iterator<Item = Visit> + 'a {
+        graph.neighbors[self.cave]
+            .iter()
+            .filter(move |next| self.can_visit(graph, rules, **next))
+            .map(move |next| self.next(graph, *next))
+    }
+}
+
+/// Counts the paths without walking each one, by remembering how many paths there are to the end
+/// from every visit.
+fn count_paths(graph: &Graph, rules: &Rules) -> usize {
+    if rules.forbidden[rules.start] {
+        return 0;
     }
-    paths
+    let mut memo = HashMap::new();
+    count_from(graph, rules, Visit::start(graph, rules.start), &mut memo)
 }
 
-fn part1(graph: Graph) -> Result<usize> {
-    let mut path = Vec::new();
-    path.push(graph.start.clone());
-    let paths = find_paths(&graph, &path, &can_visit_part1);
+fn count_from(
+    graph: &Graph,
+    rules: &Rules,
+    visit: Visit,
+    memo: &mut HashMap<Visit, usize>,
+) -> usize {
+    // paths usually stop at the end, but can carry on through it if it may be revisited
+    let at_end = visit.cave == rules.end;
+    if at_end && rules.revisits[rules.end] == 0 {
+        return 1;
+    }
+    if let Some(count) = memo.get(&visit) {
+        return *count;
+    }
 
-    Ok(paths.len())
+    let count = at_end as usize
+        + visit
+            .neighbors(graph, rules)
+            .map(|next| count_from(graph, rules, next, memo))
+            .sum::<usize>();
+    memo.insert(visit, count);
+    count
 }
 
-fn part2(graph: Graph) -> Result<usize> {
-    let mut path = Vec::new();
-    path.push(graph.start.clone());
-    let paths = find_paths(&graph, &path, &can_visit_part2);
+/// A step along a path being listed. Paths finish with `Done` after a visit to the end, so they can
+/// also carry on through the end when it may be revisited.
+#[derive(Debug, Clone)]
+enum Step {
+    At(Visit),
+    Done,
+}
 
-    Ok(paths.len())
+/// Walks every path, calling `on_path` with the caves along each one, and returns how many there
+/// were.
+fn walk_paths<P>(graph: &Graph, rules: &Rules, mut on_path: P) -> usize
+where
+    P: FnMut(&[usize]),
+{
+    if rules.forbidden[rules.start] {
+        return 0;
+    }
+    all_paths(
+        Step::At(Visit::start(graph, rules.start)),
+        |step| {
+            let visit = match step {
+                Step::At(visit) => visit,
+                Step::Done => return Vec::new(),
+            };
+            let mut steps = Vec::new();
+            if visit.cave == rules.end {
+                steps.push((Step::Done, 1));
+                if rules.revisits[rules.end] == 0 {
+                    return steps;
+                }
+            }
+            steps.extend(
+                visit
+                    .neighbors(graph, rules)
+                    .map(|next| (Step::At(next), 1)),
+            );
+            steps
+        },
+        |step| matches!(step, Step::Done),
+        |path| {
+            let caves = path
+                .iter()
+                .filter_map(|step| match step {
+                    Step::At(visit) => Some(visit.cave),
+                    Step::Done => None,
+                })
+                .collect::<Vec<usize>>();
+            on_path(&caves);
+        },
+    )
 }
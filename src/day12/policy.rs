@@ -0,0 +1,162 @@
+use super::Graph;
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+/// Which caves a path may go through, and how often. Big caves can always be visited any number of
+/// times, unless they're forbidden.
+///
+/// Parsed from a comma separated list of settings, applied in order:
+/// - `part1` or `part2`: the rules from the puzzle
+/// - `revisits=N`: how many times a small cave may be visited after the first
+/// - `revisits:<cave>=N`: the same, for one small cave, including the start or end
+/// - `caves=N`: how many different small caves a path may revisit
+/// - `forbid=<cave>`: no path may go through the cave
+/// - `start`, `end`: paths may go back through the start or end, e.g. `start-A-start-b-end`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RevisitPolicy {
+    pub revisits: usize,
+    /// Overrides `revisits` for particular caves
+    pub cave_revisits: BTreeMap<String, usize>,
+    pub max_revisited_caves: usize,
+    pub forbidden: BTreeSet<String>,
+    pub revisit_start: bool,
+    pub revisit_end: bool,
+}
+
+impl RevisitPolicy {
+    /// No small cave may be visited more than once.
+    pub fn part1() -> RevisitPolicy {
+        RevisitPolicy {
+            revisits: 0,
+            cave_revisits: BTreeMap::new(),
+            max_revisited_caves: usize::MAX,
+            forbidden: BTreeSet::new(),
+            revisit_start: false,
+            revisit_end: false,
+        }
+    }
+
+    /// A single small cave other than the start and end may be visited twice.
+    pub fn part2() -> RevisitPolicy {
+        RevisitPolicy {
+            revisits: 1,
+            max_revisited_caves: 1,
+            ..RevisitPolicy::part1()
+        }
+    }
+
+    /// Resolves the caves named in the policy against `graph`.
+    pub(super) fn rules(&self, graph: &Graph) -> Result<Rules> {
+        let start = graph.id("start")?;
+        let end = graph.id("end")?;
+
+        let mut revisits = (0..graph.names.len())
+            .map(|cave| {
+                if graph.is_big(cave) {
+                    usize::MAX
+                } else {
+                    self.revisits
+                }
+            })
+            .collect::<Vec<usize>>();
+        if !self.revisit_start {
+            revisits[start] = 0;
+        }
+        if !self.revisit_end {
+            revisits[end] = 0;
+        }
+        for (name, cave_revisits) in &self.cave_revisits {
+            let cave = graph.id(name)?;
+            if graph.is_big(cave) {
+                return Err(anyhow::Error::msg(format!(
+                    "'{}' is a big cave, which can always be revisited",
+                    name
+                )));
+            }
+            revisits[cave] = *cave_revisits;
+        }
+
+        let mut forbidden = vec![false; graph.names.len()];
+        for name in &self.forbidden {
+            forbidden[graph.id(name)?] = true;
+        }
+
+        Ok(Rules {
+            start,
+            end,
+            revisits,
+            max_revisited_caves: self.max_revisited_caves,
+            forbidden,
+        })
+    }
+
+    fn apply(&mut self, setting: &str) -> Result<()> {
+        let (key, value) = match setting.split_once('=') {
+            Some((key, value)) => (key.trim(), Some(value.trim())),
+            None => (setting, None),
+        };
+        let number = || -> Result<usize> {
+            let value = value.ok_or_else(|| {
+                anyhow::Error::msg(format!("Policy setting '{}' needs a value", key))
+            })?;
+            value.parse().map_err(|_| {
+                anyhow::Error::msg(format!(
+                    "Expected a number for '{}' but got '{}'",
+                    key, value
+                ))
+            })
+        };
+
+        match (key, value) {
+            ("part1", None) => *self = RevisitPolicy::part1(),
+            ("part2", None) => *self = RevisitPolicy::part2(),
+            ("start", None) => self.revisit_start = true,
+            ("end", None) => self.revisit_end = true,
+            ("revisits", _) => self.revisits = number()?,
+            ("caves", _) => self.max_revisited_caves = number()?,
+            ("forbid", Some(cave)) => {
+                self.forbidden.insert(cave.to_owned());
+            }
+            (key, _) if key.starts_with("revisits:") => {
+                let cave = key.trim_start_matches("revisits:").trim();
+                self.cave_revisits.insert(cave.to_owned(), number()?);
+            }
+            _ => {
+                return Err(anyhow::Error::msg(format!(
+                    "Unrecognized policy setting '{}'",
+                    setting
+                )))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for RevisitPolicy {
+    fn default() -> RevisitPolicy {
+        RevisitPolicy::part1()
+    }
+}
+
+impl FromStr for RevisitPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<RevisitPolicy> {
+        let mut policy = RevisitPolicy::default();
+        for setting in s.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            policy.apply(setting)?;
+        }
+        Ok(policy)
+    }
+}
+
+/// A policy resolved against a particular graph, indexed by cave id.
+#[derive(Debug)]
+pub(super) struct Rules {
+    pub start: usize,
+    pub end: usize,
+    pub revisits: Vec<usize>,
+    pub max_revisited_caves: usize,
+    pub forbidden: Vec<bool>,
+}
@@ -1,125 +1,272 @@
-use crate::arguments::Arguments;
-use crate::common::position::Position;
-use crate::io::reader;
-use anyhow::Result;
+use crate::common::parse::{InputLine, ParseError};
+use crate::common::point::Position;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Part, Solver};
+use anyhow::{Context, Result};
 use itertools::Itertools;
-use std::io::BufRead;
-
-pub fn day13(args: &Arguments) -> Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
-
-    let mut section = 0;
-    let mut positions = Vec::new();
-    let mut folds = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        if line.trim().is_empty() {
-            section += 1;
-            continue;
-        }
-        if section == 0 {
-            positions.push(Position::from_comma_separated_line(line));
-        } else {
-            folds.push(Fold::from_line(line)?)
-        }
-    }
-    let positions = positions;
-    let folds = folds;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
 
-    let result = if args.part == 1 {
-        part1(positions, folds)
-    } else {
-        part2(positions, folds)
-    }?;
+const EXAMPLE: &str = "\
+6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
 
-    info!("{:?}", result);
+fold along y=7
+fold along x=5
+";
 
-    Ok(())
+/// Where to write the sheet after each fold.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Frames {
+    /// Stderr, so the frames don't get mixed in with the answers on stdout
+    Stderr,
+    /// Numbered text files in this directory, one per fold
+    Dir(String),
 }
 
-enum Fold {
-    Vertical(usize),
-    Horizontal(usize),
-}
+/// Passing this as the frames destination writes them to stderr.
+pub const STDERR: &str = "-";
 
-impl Fold {
-    fn from_line(line: String) -> Result<Fold> {
-        let line = line.replace("fold along ", "");
-        let parts = line.split("=").into_iter().collect_vec();
-
-        match *parts.get(0).unwrap() {
-            "x" => Ok(Fold::Vertical(parts.get(1).unwrap().parse().unwrap())),
-            "y" => Ok(Fold::Horizontal(parts.get(1).unwrap().parse().unwrap())),
-            _ => Err(anyhow::Error::msg("Invalid fold")),
+impl FromStr for Frames {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Frames> {
+        match s {
+            STDERR => Ok(Frames::Stderr),
+            _ => Ok(Frames::Dir(s.to_owned())),
         }
     }
 }
 
-fn fold_position(p: &Position, fold: &Fold) -> Position {
-    match fold {
-        Fold::Horizontal(y) => {
-            if p.y < *y {
-                p.to_owned()
-            } else {
-                Position::new(p.x, y - (p.y - y))
+#[derive(Debug, Default)]
+pub struct Day13 {
+    frames: Option<Frames>,
+}
+
+impl Day13 {
+    /// `frames` also writes out the sheet before folding and after every fold.
+    pub fn new(frames: Option<Frames>) -> Day13 {
+        Day13 { frames }
+    }
+
+    fn fold(&self, part: Part, positions: Vec<Position>, folds: &[Fold]) -> Result<Sheet> {
+        let mut sheet = Sheet::new(positions);
+        self.show(part, 0, None, &sheet)?;
+        for (i, fold) in folds.iter().enumerate() {
+            sheet = sheet.fold(fold)?;
+            self.show(part, i + 1, Some(fold), &sheet)?;
+        }
+        Ok(sheet)
+    }
+
+    fn show(&self, part: Part, step: usize, fold: Option<&Fold>, sheet: &Sheet) -> Result<()> {
+        let description = match fold {
+            Some(fold) => format!("after folding along {}", fold),
+            None => String::from("before folding"),
+        };
+        info!(
+            "Part {} {}: {}x{} with {} dots",
+            part,
+            description,
+            sheet.width,
+            sheet.height,
+            sheet.dots.len()
+        );
+
+        match &self.frames {
+            None => {}
+            Some(Frames::Stderr) => eprintln!("Part {} {}:\n{}\n", part, description, sheet),
+            Some(Frames::Dir(dir)) => {
+                fs::create_dir_all(dir).with_context(|| format!("Unable to create {}", dir))?;
+                let file = Path::new(dir).join(format!("day13-part{}-{:03}.txt", part, step));
+                fs::write(&file, format!("{}\n", sheet))
+                    .with_context(|| format!("Unable to write {}", file.display()))?;
             }
         }
-        Fold::Vertical(x) => {
-            if p.x < *x {
-                p.to_owned()
+        Ok(())
+    }
+}
+
+impl Solver for Day13 {
+    type Input = (Vec<Position>, Vec<Fold>);
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let mut section = 0;
+        let mut positions = Vec::new();
+        let mut folds = Vec::new();
+        for line in input.lines() {
+            let line = line?;
+            if line.text.trim().is_empty() {
+                section += 1;
+                continue;
+            }
+            if section == 0 {
+                positions.push(Position::from_input(&line, &line.text)?);
             } else {
-                Position::new(x - (p.x - x), p.y)
+                folds.push(Fold::from_line(&line)?)
             }
         }
+
+        Ok((positions, folds))
+    }
+
+    fn part1(&self, (positions, folds): Self::Input) -> Result<Answer> {
+        let first = folds
+            .first()
+            .ok_or_else(|| anyhow::Error::msg("There are no folds"))?;
+        let sheet = self.fold(Part::One, positions, std::slice::from_ref(first))?;
+        Ok(Answer::from(sheet.dots.len()))
+    }
+
+    fn part2(&self, (positions, folds): Self::Input) -> Result<Answer> {
+        let sheet = self.fold(Part::Two, positions, &folds)?;
+        // The answer is whatever letters the folded paper spells out
+        Ok(Answer::from(sheet.trimmed().to_string()))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            EXAMPLE,
+            17usize,
+            String::from("#####\n#...#\n#...#\n#...#\n#####"),
+        )]
     }
 }
 
-fn fold_once(positions: Vec<Position>, fold: &Fold) -> Vec<Position> {
-    positions
-        .into_iter()
-        .map(|p| fold_position(&p, fold))
-        .unique()
-        .collect()
+pub enum Fold {
+    Vertical(usize),
+    Horizontal(usize),
 }
 
-fn fold(positions: Vec<Position>, folds: &[Fold]) -> Vec<Position> {
-    let mut positions = positions;
-    for f in folds {
-        positions = fold_once(positions, f);
+impl Fold {
+    fn from_line(line: &InputLine) -> Result<Fold, ParseError> {
+        let fold = line
+            .text
+            .strip_prefix("fold along ")
+            .ok_or_else(|| line.error(&line.text, "Expected 'fold along'"))?;
+        let (axis, value) = line.split_once(fold, "=")?;
+
+        match axis {
+            "x" => Ok(Fold::Vertical(line.parse(value)?)),
+            "y" => Ok(Fold::Horizontal(line.parse(value)?)),
+            _ => Err(line.error(axis, format!("Invalid fold axis '{}'", axis))),
+        }
     }
+}
 
-    positions
+impl fmt::Display for Fold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fold::Vertical(x) => write!(f, "x={}", x),
+            Fold::Horizontal(y) => write!(f, "y={}", y),
+        }
+    }
 }
 
-fn part1(positions: Vec<Position>, folds: Vec<Fold>) -> Result<usize> {
-    let positions = fold_once(positions, folds.first().unwrap());
-    Ok(positions.len())
+/// The transparent paper and the dots on it.
+#[derive(Debug, Clone)]
+struct Sheet {
+    width: usize,
+    height: usize,
+    dots: Vec<Position>,
 }
 
-fn part2(positions: Vec<Position>, folds: Vec<Fold>) -> Result<usize> {
-    // fold everything
-    let positions = fold(positions, &folds);
+impl Sheet {
+    /// Just big enough for the dots.
+    fn new(dots: Vec<Position>) -> Sheet {
+        let width = dots.iter().map(|p| p.x + 1).max().unwrap_or(0);
+        let height = dots.iter().map(|p| p.y + 1).max().unwrap_or(0);
+        let dots = dots.into_iter().unique().collect();
+        Sheet {
+            width,
+            height,
+            dots,
+        }
+    }
 
-    // Display the result
-    let max_x = positions.iter().map(|p| p.x).max().unwrap();
-    let max_y = positions.iter().map(|p| p.y).max().unwrap();
+    /// Folds the bottom half up, or the right half left. If that half is the bigger one, it sticks
+    /// out past the other edge and the sheet grows to fit it.
+    fn fold(&self, fold: &Fold) -> Result<Sheet> {
+        let (line, width, height) = match fold {
+            Fold::Horizontal(y) => (*y, self.width, folded_length(self.height, *y)),
+            Fold::Vertical(x) => (*x, folded_length(self.width, *x), self.height),
+        };
+        let dots = self
+            .dots
+            .iter()
+            .map(|p| {
+                let folded = match fold {
+                    Fold::Horizontal(_) => {
+                        fold_coordinate(p.y, line, height).map(|y| Position::new(p.x, y))
+                    }
+                    Fold::Vertical(_) => {
+                        fold_coordinate(p.x, line, width).map(|x| Position::new(x, p.y))
+                    }
+                };
+                folded.ok_or_else(|| {
+                    anyhow::Error::msg(format!("Dot at {} is on the fold along {}", p, fold))
+                })
+            })
+            .collect::<Result<Vec<Position>>>()?;
 
-    let mut result: Vec<Vec<String>> = Vec::new();
-    for y in 0..max_y + 1 {
-        result.push(Vec::new());
-        for _x in 0..max_x + 1 {
-            result[y].push(String::from("."));
-        }
+        Ok(Sheet {
+            width,
+            height,
+            dots: dots.into_iter().unique().collect(),
+        })
     }
 
-    for p in positions {
-        result[p.y][p.x] = String::from("#");
+    /// Cut down to just the dots, so no blank rows or columns are left at the right or bottom.
+    fn trimmed(&self) -> Sheet {
+        Sheet::new(self.dots.clone())
     }
+}
 
-    for row in result {
-        info!("{}", row.join(""));
+/// The length of a side of `length` after folding it along `line`: the longer of the two parts.
+fn folded_length(length: usize, line: usize) -> usize {
+    line.max(length.saturating_sub(line + 1))
+}
+
+/// Where `c` ends up after folding along `line` into a side of `length`, or `None` if it's on the
+/// line itself.
+fn fold_coordinate(c: usize, line: usize, length: usize) -> Option<usize> {
+    if c < line {
+        Some(c + (length - line))
+    } else if c > line {
+        Some(length - (c - line))
+    } else {
+        None
     }
+}
 
-    // This isn't used this time.
-    Ok(0)
+impl fmt::Display for Sheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows = vec![vec!['.'; self.width]; self.height];
+        for p in &self.dots {
+            rows[p.y][p.x] = '#';
+        }
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().collect::<String>())
+            .join("\n");
+        write!(f, "{}", rows)
+    }
 }
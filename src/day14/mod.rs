@@ -1,67 +1,123 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::common::parse::{InputLine, ParseError};
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
 use itertools::Itertools;
 use std::collections::HashMap;
-use std::io::BufRead;
 use std::iter::FromIterator;
 
-pub fn day14(args: &Arguments) -> anyhow::Result<()> {
-    let mut reader = reader(args.src_file.as_ref())?;
-
-    let mut poly_template = String::new();
-    reader.read_line(&mut poly_template)?;
-    poly_template = poly_template.trim().to_owned();
+const EXAMPLE: &str = "\
+NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C
+";
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Input = (String, HashMap<String, char>);
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let mut lines = input.lines();
+        let template_line = lines.expect_line()?;
+        let poly_template = template_line.text.trim();
+        if poly_template.is_empty() {
+            return Err(template_line
+                .error_at(1, "Expected a polymer template")
+                .into());
+        }
 
-    let mut lines = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        if line.is_empty() {
-            continue;
+        let mut insertion_lines = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.text.is_empty() {
+                continue;
+            }
+            insertion_lines.push(line);
+        }
+        let pairs = parse_insertion_pairs(&insertion_lines)?;
+
+        let template_chars = poly_template.char_indices().collect_vec();
+        for window in template_chars.windows(2) {
+            let (start, end) = (window[0].0, window[1].0 + window[1].1.len_utf8());
+            let pair = &poly_template[start..end];
+            if !pairs.contains_key(pair) {
+                return Err(template_line
+                    .error(pair, format!("There's no insertion rule for '{}'", pair))
+                    .into());
+            }
         }
-        lines.push(line);
+
+        Ok((poly_template.to_owned(), pairs))
     }
-    let pairs = parse_insertion_pairs(lines);
 
-    let result = if args.part == 1 {
-        build_polymer(poly_template, pairs, 10)
-    } else {
-        build_polymer(poly_template, pairs, 40)
-    }?;
+    fn part1(&self, (poly_template, pairs): Self::Input) -> Result<Answer> {
+        build_polymer(poly_template, pairs, 10).map(Answer::from)
+    }
 
-    info!("{:?}", result);
+    fn part2(&self, (poly_template, pairs): Self::Input) -> Result<Answer> {
+        build_polymer(poly_template, pairs, 40).map(Answer::from)
+    }
 
-    Ok(())
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 1588u64, 2188189693529u64)]
+    }
 }
 
-fn parse_insertion_pairs(lines: Vec<String>) -> HashMap<String, char> {
+fn parse_insertion_pairs(lines: &[InputLine]) -> Result<HashMap<String, char>, ParseError> {
     let mut pairs = HashMap::new();
 
     for line in lines {
-        let mut parts = line
-            .split("->")
-            .map(|s| s.trim().to_owned())
-            .collect::<Vec<String>>();
-
-        pairs.insert(parts.remove(0), parts.remove(0).chars().nth(0).unwrap());
+        let (pair, element) = line.split_once(&line.text, "->")?;
+        let (pair, element) = (pair.trim(), element.trim());
+        if pair.chars().count() != 2 {
+            return Err(line.error(pair, "Expected a pair of elements"));
+        }
+        let mut element_chars = element.chars();
+        match (element_chars.next(), element_chars.next()) {
+            (Some(c), None) => {
+                pairs.insert(pair.to_owned(), c);
+            }
+            _ => return Err(line.error(element, "Expected a single element")),
+        }
     }
 
-    pairs
+    Ok(pairs)
 }
 
 fn step(
     pair_counts: HashMap<String, u64>,
     char_counts: HashMap<char, u64>,
     pairs: &HashMap<String, char>,
-) -> (HashMap<String, u64>, HashMap<char, u64>) {
+) -> Result<(HashMap<String, u64>, HashMap<char, u64>)> {
     let mut new_pair_counts = pair_counts.clone();
     let mut new_char_counts = char_counts.clone();
 
     for entry in pair_counts {
-        let left = entry.0.chars().nth(0).unwrap();
+        let left = entry.0.chars().next().unwrap();
         let right = entry.0.chars().nth(1).unwrap();
 
-        let middle = pairs.get(&entry.0).unwrap();
+        let middle = pairs.get(&entry.0).ok_or_else(|| {
+            anyhow::Error::msg(format!(
+                "There's no insertion rule for '{}', which an insertion produced",
+                entry.0
+            ))
+        })?;
 
         // Decrement old pair
         let count = new_pair_counts.entry(entry.0).or_default();
@@ -83,7 +139,7 @@ fn step(
         *count += entry.1;
     }
 
-    (new_pair_counts, new_char_counts)
+    Ok((new_pair_counts, new_char_counts))
 }
 
 fn build_polymer(
@@ -112,7 +168,7 @@ fn build_polymer(
 
     let mut result = (pair_counts, char_counts);
     for _ in 0..num_steps {
-        result = step(result.0, result.1, &pairs);
+        result = step(result.0, result.1, &pairs)?;
     }
 
     let max = result.1.values().max().unwrap();
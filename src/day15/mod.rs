@@ -1,226 +1,208 @@
-use crate::arguments::Arguments;
-use crate::common::position::Position;
-use crate::io::reader;
+use crate::common::grid::Grid;
+use crate::common::point::{Point2, Position};
+use crate::common::render::Render;
+use crate::common::search::{dijkstra_dense, SearchResult};
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Part, Solver};
 use anyhow::Result;
-use itertools::Itertools;
-use std::cmp::{Ordering, Reverse};
-use std::collections::{BinaryHeap, HashMap, HashSet};
-use std::io::BufRead;
-
-pub fn day15(args: &Arguments) -> Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
-
-    let mut lines = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        let line = line
-            .chars()
-            .map(|c| String::from(c).parse().unwrap())
-            .collect::<Vec<usize>>();
-        lines.push(line);
-    }
-    let lines = lines;
-
-    let result = if args.part == 1 {
-        part1(lines)
-    } else {
-        part2(lines)
-    }?;
-
-    info!("{:?}", result);
-
-    Ok(())
-}
-
-fn get_max_x(board: &Vec<Vec<usize>>) -> usize {
-    board.iter().map(|x| x.len()).max().unwrap()
-}
-
-fn get_max_y(board: &Vec<Vec<usize>>) -> usize {
-    board.len()
-}
 
-#[derive(Debug, Default)]
-struct Node {
-    visited: bool,
-    tentative_distance: usize,
-    cost: usize,
-    position: Position,
-}
+const EXAMPLE: &str = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581
+";
+
+/// How many copies of the cave make up the full map in each direction.
+pub const DEFAULT_TILE_FACTOR: usize = 5;
+
+#[derive(Debug)]
+pub struct Day15 {
+    tile_factor: usize,
+    render: Option<Render>,
+}
+
+impl Day15 {
+    /// `tile_factor` sets the size of the full map for part 2, and `render` also writes out the
+    /// lowest risk path for each part.
+    pub fn new(tile_factor: usize, render: Option<Render>) -> Day15 {
+        Day15 {
+            tile_factor,
+            render,
+        }
+    }
 
-impl Eq for Node {}
+    /// The lowest total risk path from the top left to the bottom right of the map for `part`,
+    /// with its total risk. The risk of the starting cell isn't counted.
+    pub fn lowest_risk_path(
+        &self,
+        part: Part,
+        grid: &Grid<usize>,
+    ) -> Result<SearchResult<Position, usize>> {
+        let map = self.map(part, grid)?;
+        let route = lowest_risk_indices(&map)?;
+        Ok(SearchResult {
+            cost: route.cost as usize,
+            path: route.path.iter().map(|i| map.position_of(*i)).collect(),
+        })
+    }
 
-impl PartialEq<Self> for Node {
-    fn eq(&self, other: &Self) -> bool {
-        self.tentative_distance.eq(&other.tentative_distance) && self.position.eq(&other.position)
+    fn map<'a>(&self, part: Part, grid: &'a Grid<usize>) -> Result<TiledGrid<'a>> {
+        match part {
+            Part::One => TiledGrid::new(grid, 1),
+            Part::Two => TiledGrid::new(grid, self.tile_factor),
+        }
     }
-}
 
-impl PartialOrd<Self> for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.tentative_distance
-            .partial_cmp(&other.tentative_distance)
+    fn solve(&self, part: Part, grid: &Grid<usize>) -> Result<usize> {
+        let route = self.lowest_risk_path(part, grid)?;
+
+        if let Some(render) = &self.render {
+            let map = self.map(part, grid)?;
+            let file = render.write_path(
+                &format!("day15-part{}", part),
+                (map.width(), map.height()),
+                |p| map.risk(p),
+                &route.path,
+                brightness,
+            )?;
+            info!("Wrote the part {} path to {}", part, file.display());
+        }
+
+        Ok(route.cost)
     }
 }
 
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.tentative_distance.cmp(&other.tentative_distance)
+impl Default for Day15 {
+    fn default() -> Day15 {
+        Day15::new(DEFAULT_TILE_FACTOR, None)
     }
 }
 
-#[derive(Debug, Default)]
-struct Point {
-    position: Position,
-    tentative_total: usize,
-}
+impl Solver for Day15 {
+    type Input = Grid<usize>;
 
-impl Point {
-    fn new(position: Position, tentative_total: usize) -> Point {
-        Point {
-            position,
-            tentative_total,
-        }
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        Grid::<u8>::from_digit_lines(input).map(|grid| grid.map(usize::from))
     }
-}
 
-impl Eq for Point {}
+    fn part1(&self, grid: Self::Input) -> Result<Answer> {
+        self.solve(Part::One, &grid).map(Answer::from)
+    }
 
-impl PartialEq<Self> for Point {
-    fn eq(&self, other: &Self) -> bool {
-        self.tentative_total.eq(&other.tentative_total)
+    fn part2(&self, grid: Self::Input) -> Result<Answer> {
+        self.solve(Part::Two, &grid).map(Answer::from)
     }
-}
 
-impl PartialOrd<Self> for Point {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.tentative_total.partial_cmp(&other.tentative_total)
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 40usize, 315usize)]
     }
 }
 
-impl Ord for Point {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.tentative_total.cmp(&other.tentative_total)
+/// The full map: `factor` x `factor` copies of the cave, with the risk going up by one for each
+/// tile right or down and wrapping back around to 1 after 9. Risks are worked out from the
+/// original cave on demand rather than stored.
+struct TiledGrid<'a> {
+    grid: &'a Grid<usize>,
+    width: usize,
+    height: usize,
+}
+
+impl<'a> TiledGrid<'a> {
+    /// Fails if the full map has more cells than fit in a `usize`.
+    fn new(grid: &'a Grid<usize>, factor: usize) -> Result<TiledGrid<'a>> {
+        let too_big = || {
+            anyhow::Error::msg(format!(
+                "A {}x{} cave tiled {} times each way is too big",
+                grid.width(),
+                grid.height(),
+                factor
+            ))
+        };
+        let width = grid.width().checked_mul(factor).ok_or_else(too_big)?;
+        let height = grid.height().checked_mul(factor).ok_or_else(too_big)?;
+        width.checked_mul(height).ok_or_else(too_big)?;
+        Ok(TiledGrid {
+            grid,
+            width,
+            height,
+        })
     }
-}
 
-// This can definitely be improved...
-fn dijkstra2(
-    lines: &Vec<Vec<usize>>,
-    start: &Position,
-    end: &Position,
-    max_x: usize,
-    max_y: usize,
-) -> usize {
-    let mut nodes = HashMap::new();
-    for y in 0..lines.len() {
-        for x in 0..lines[y].len() {
-            let position = Position::new(x, y);
-            let node = Node {
-                position: position.clone(),
-                tentative_distance: usize::MAX,
-                cost: lines[y][x],
-                ..Default::default()
-            };
-            nodes.insert(position, node);
-        }
+    fn width(&self) -> usize {
+        self.width
     }
-    let mut heap = BinaryHeap::new();
 
-    nodes
-        .entry(start.clone())
-        .and_modify(|x| x.tentative_distance = 0);
-    heap.push(Reverse(Point::new(start.clone(), 0)));
+    fn height(&self) -> usize {
+        self.height
+    }
 
-    loop {
-        let current = heap.pop();
-        if current.is_none() {
-            break;
-        }
-        let current = current.unwrap().0;
-        if current.position.eq(end) {
-            break;
-        }
+    fn len(&self) -> usize {
+        self.width * self.height
+    }
 
-        // there may be duplicate positions in the heap, so track visited nodes and skip nodes
-        // that were already visited
-        // todo: is there a better way to do this while keeping the performance benefits
-        //  of the heap?
-        if nodes.get(&current.position).unwrap().visited {
-            continue;
-        }
-        nodes
-            .entry(current.position.clone())
-            .and_modify(|x| x.visited = true);
-        let adjacent = current
-            .position
-            .adjacent(max_x, max_y)
-            .into_iter()
-            .filter(|p| !nodes.get(p).unwrap().visited)
-            .collect_vec();
-
-        for p in adjacent {
-            let new_tentative = nodes.get(&current.position).unwrap().tentative_distance
-                + nodes.get(&p).unwrap().cost;
-            if new_tentative < nodes.get(&p).unwrap().tentative_distance {
-                nodes
-                    .entry(p.clone())
-                    .and_modify(|x| x.tentative_distance = new_tentative);
-                heap.push(Reverse(Point::new(p, new_tentative)))
-            }
+    fn risk(&self, p: Position) -> usize {
+        let increase_amount = p.x / self.grid.width() + p.y / self.grid.height();
+        let value = self.grid[Position::new(p.x % self.grid.width(), p.y % self.grid.height())];
+        if increase_amount == 0 {
+            // the original cave as it is, which may have a risk of 0
+            return value;
         }
+        (value + increase_amount - 1) % 9 + 1
     }
 
-    nodes.get(end).unwrap().tentative_distance
-}
-
-fn part1(lines: Vec<Vec<usize>>) -> Result<usize> {
-    let max_x = get_max_x(&lines);
-    let max_y = get_max_y(&lines);
-
-    let start = Position::new(0, 0);
-    let end = Position::new(max_x - 1, max_y - 1);
-
-    Ok(dijkstra2(&lines, &start, &end, max_x, max_y))
-}
+    fn index_of(&self, p: Position) -> usize {
+        p.y * self.width() + p.x
+    }
 
-fn sub_board(original: &Vec<Vec<usize>>, increase_amount: usize) -> Vec<Vec<usize>> {
-    let mut sub = Vec::new();
-
-    for y in 0..original.len() {
-        sub.push(Vec::new());
-        for x in 0..original[y].len() {
-            let mut new_value = original[y][x];
-            for _ in 0..increase_amount {
-                new_value += 1;
-                if new_value > 9 {
-                    new_value = 1;
-                }
-            }
-
-            sub[y].push(new_value);
-        }
+    fn position_of(&self, index: usize) -> Position {
+        Position::new(index % self.width(), index / self.width())
     }
 
-    sub
+    /// Indices of the cells above, below, left and right of the cell at `index`.
+    fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let p = self.position_of(index);
+        Point2::<isize>::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |d| {
+                Some(Position::new(
+                    p.x.checked_add_signed(d.x)?,
+                    p.y.checked_add_signed(d.y)?,
+                ))
+            })
+            .filter(|next| next.x < self.width() && next.y < self.height())
+            .map(|next| self.index_of(next))
+    }
 }
 
-fn part2(lines: Vec<Vec<usize>>) -> Result<usize> {
-    let max_y = get_max_y(&lines);
+/// Lower risk cells are lighter.
+fn brightness(risk: &usize) -> u8 {
+    (255 - (risk * 25).min(255)) as u8
+}
 
-    let mut big_board: Vec<Vec<usize>> = Vec::new();
-    for _y in 0..(5 * max_y) {
-        big_board.push(Vec::new());
+/// The lowest total risk path from the top left to the bottom right, as indices into `map`. The
+/// risk of the starting cell isn't counted. Risks are summed as `u32` to keep the cost array small
+/// for big maps.
+fn lowest_risk_indices(map: &TiledGrid) -> Result<SearchResult<usize, u32>> {
+    if map.len() == 0 {
+        return Err(anyhow::Error::msg("The cave is empty"));
     }
-
-    for big_y in 0..5 {
-        for big_x in 0..5 {
-            let mut sub_board = sub_board(&lines, big_x + big_y);
-            for sub_y in 0..sub_board.len() {
-                big_board[(max_y * big_y) + sub_y].append(&mut sub_board[sub_y])
-            }
-        }
-    }
-
-    part1(big_board)
+    let end = map.len() - 1;
+
+    dijkstra_dense(
+        map.len(),
+        0,
+        |i| {
+            map.neighbors(i)
+                .map(|next| (next, map.risk(map.position_of(next)) as u32))
+        },
+        |i| i == end,
+    )
+    .ok_or_else(|| anyhow::Error::msg("No path to the bottom right"))
 }
@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+
+const HEX_DIGIT_BITS: usize = 4;
+
+#[derive(Debug)]
+pub enum BitReaderError {
+    /// The transmission ended `wanted` bits into a read starting at bit `position`
+    Truncated {
+        position: usize,
+        wanted: usize,
+    },
+    /// `index` counts characters from the start of the transmission, from 0
+    InvalidCharacter {
+        index: usize,
+        character: char,
+    },
+    Io(io::Error),
+}
+
+impl fmt::Display for BitReaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitReaderError::Truncated { position, wanted } => write!(
+                f,
+                "Transmission ended while reading {} bits at bit {}",
+                wanted, position
+            ),
+            BitReaderError::InvalidCharacter { index, character } => write!(
+                f,
+                "Expected a hex digit but got '{}' at character {}",
+                character,
+                index + 1
+            ),
+            BitReaderError::Io(e) => write!(f, "Unable to read transmission: {}", e),
+        }
+    }
+}
+
+impl Error for BitReaderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BitReaderError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BitReaderError {
+    fn from(e: io::Error) -> BitReaderError {
+        BitReaderError::Io(e)
+    }
+}
+
+/// Reads a hex transmission a bit at a time, most significant bit of each digit first. Digits are
+/// only read from the underlying reader as they're needed, and a line break or the end of the
+/// reader ends the transmission.
+pub struct BitReader<R> {
+    reader: R,
+    /// The digit currently being read
+    digit: u8,
+    /// How many bits of `digit` haven't been read yet
+    digit_bits: usize,
+    /// How many bits have been read so far
+    position: usize,
+    /// How many characters have been read so far
+    index: usize,
+}
+
+impl<R> BitReader<R>
+where
+    R: BufRead,
+{
+    pub fn new(reader: R) -> BitReader<R> {
+        BitReader {
+            reader,
+            digit: 0,
+            digit_bits: 0,
+            position: 0,
+            index: 0,
+        }
+    }
+
+    /// The number of bits read so far, i.e. the offset of the next bit.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, BitReaderError> {
+        Ok(self.read_bits(1)? == 1)
+    }
+
+    /// Reads `num_bits` bits, at most 64, as an unsigned number, most significant bit first.
+    pub fn read_bits(&mut self, num_bits: usize) -> Result<u64, BitReaderError> {
+        assert!(num_bits <= 64, "Can't read {} bits into a u64", num_bits);
+
+        let start = self.position;
+        let mut result = 0;
+        for _ in 0..num_bits {
+            if self.digit_bits == 0 {
+                self.digit = self.next_digit()?.ok_or(BitReaderError::Truncated {
+                    position: start,
+                    wanted: num_bits,
+                })?;
+                self.digit_bits = HEX_DIGIT_BITS;
+            }
+            self.digit_bits -= 1;
+            result = (result << 1) | ((self.digit >> self.digit_bits) & 1) as u64;
+            self.position += 1;
+        }
+        Ok(result)
+    }
+
+    /// The value of the next hex digit, or `None` at the end of the transmission.
+    fn next_digit(&mut self) -> Result<Option<u8>, BitReaderError> {
+        let byte = match self.reader.fill_buf()?.first() {
+            Some(byte) => *byte,
+            None => return Ok(None),
+        };
+        if byte == b'\n' || byte == b'\r' {
+            return Ok(None);
+        }
+        self.reader.consume(1);
+
+        let character = byte as char;
+        let digit = character
+            .to_digit(16)
+            .ok_or(BitReaderError::InvalidCharacter {
+                index: self.index,
+                character,
+            })?;
+        self.index += 1;
+        Ok(Some(digit as u8))
+    }
+}
@@ -0,0 +1,117 @@
+use super::{
+    Contents, LengthTypeId, Packet, TypeId, LITERAL_GROUP_BITS, NUM_PACKETS_BITS,
+    TOTAL_LENGTH_BITS, TYPE_ID_BITS, VERSION_BITS,
+};
+use anyhow::Result;
+use bitvec::prelude::*;
+use num_bigint::BigUint;
+
+/// Encodes `packet` as a hex transmission, padded with zeros to a whole number of hex digits.
+pub fn encode(packet: &Packet) -> Result<String> {
+    let mut bits = bitvec![Msb0, u64;];
+    write_packet(&mut bits, packet)?;
+    while bits.len() % 4 != 0 {
+        bits.push(false);
+    }
+
+    let hex = bits
+        .chunks(4)
+        .map(|nibble| {
+            let digit = nibble
+                .iter()
+                .fold(0, |accum, bit| (accum << 1) | *bit as u32);
+            std::char::from_digit(digit, 16)
+                .unwrap()
+                .to_ascii_uppercase()
+        })
+        .collect();
+    Ok(hex)
+}
+
+/// Appends the lowest `num_bits` bits of `value`, most significant bit first.
+fn push_number(bits: &mut BitVec<Msb0, u64>, value: u64, num_bits: usize) -> Result<()> {
+    if num_bits < 64 && value >> num_bits != 0 {
+        return Err(anyhow::Error::msg(format!(
+            "{} doesn't fit in {} bits",
+            value, num_bits
+        )));
+    }
+    for i in (0..num_bits).rev() {
+        bits.push((value >> i) & 1 == 1);
+    }
+    Ok(())
+}
+
+fn write_packet(bits: &mut BitVec<Msb0, u64>, packet: &Packet) -> Result<()> {
+    let is_literal = matches!(
+        packet.contents,
+        Contents::Literal(_) | Contents::BigLiteral(_)
+    );
+    if is_literal != (packet.header.type_id == TypeId::Literal) {
+        return Err(anyhow::Error::msg(format!(
+            "Packet with type id {:?} has the wrong contents",
+            packet.header.type_id
+        )));
+    }
+
+    push_number(bits, packet.header.version, VERSION_BITS)?;
+    push_number(bits, packet.header.type_id.number(), TYPE_ID_BITS)?;
+
+    match &packet.contents {
+        Contents::Literal(value) => write_literal(bits, *value),
+        Contents::BigLiteral(value) => write_big_literal(bits, value),
+        Contents::Operator {
+            length_type_id,
+            packets,
+        } => write_operator(bits, *length_type_id, packets),
+    }
+}
+
+/// Uses as few groups as possible, but always at least one.
+fn write_literal(bits: &mut BitVec<Msb0, u64>, value: u64) -> Result<()> {
+    let significant_bits = 64 - value.leading_zeros() as usize;
+    let num_groups = significant_bits.div_ceil(LITERAL_GROUP_BITS).max(1);
+    for group in (0..num_groups).rev() {
+        bits.push(group > 0);
+        let group_value = (value >> (group * LITERAL_GROUP_BITS)) & 0b1111;
+        push_number(bits, group_value, LITERAL_GROUP_BITS)?;
+    }
+    Ok(())
+}
+
+fn write_big_literal(bits: &mut BitVec<Msb0, u64>, value: &BigUint) -> Result<()> {
+    let num_groups = (value.bits() as usize).div_ceil(LITERAL_GROUP_BITS).max(1);
+    for group in (0..num_groups).rev() {
+        bits.push(group > 0);
+        for i in (0..LITERAL_GROUP_BITS).rev() {
+            bits.push(value.bit((group * LITERAL_GROUP_BITS + i) as u64));
+        }
+    }
+    Ok(())
+}
+
+fn write_operator(
+    bits: &mut BitVec<Msb0, u64>,
+    length_type_id: LengthTypeId,
+    packets: &[Packet],
+) -> Result<()> {
+    match length_type_id {
+        LengthTypeId::Bits => {
+            let mut sub_packets = bitvec![Msb0, u64;];
+            for packet in packets {
+                write_packet(&mut sub_packets, packet)?;
+            }
+            bits.push(false);
+            push_number(bits, sub_packets.len() as u64, TOTAL_LENGTH_BITS)?;
+            bits.extend_from_bitslice(&sub_packets);
+        }
+        LengthTypeId::Packets => {
+            bits.push(true);
+            push_number(bits, packets.len() as u64, NUM_PACKETS_BITS)?;
+            for packet in packets {
+                write_packet(bits, packet)?;
+            }
+        }
+    }
+    Ok(())
+}
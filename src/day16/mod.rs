@@ -1,58 +1,112 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
-use anyhow::Result;
-use bitvec::prelude::*;
-use itertools::Itertools;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
+use anyhow::{Context, Result};
+use num_bigint::BigUint;
 use std::io::BufRead;
+use std::str::FromStr;
 
-pub fn day16(args: &Arguments) -> Result<()> {
-    let mut reader = reader(args.src_file.as_ref())?;
-    let mut chars = String::new();
-    reader.read_line(&mut chars)?;
+mod bit_reader;
+mod encode;
+mod print;
 
-    let chars = chars.trim().chars().collect_vec();
+pub use bit_reader::{BitReader, BitReaderError};
+pub use encode::encode;
+pub use print::{explain, infix, tree};
 
-    let result = if args.part == 1 {
-        part1(chars)
-    } else {
-        part2(chars)
-    }?;
+/// How to evaluate packets whose values don't fit in a `u64`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NumericMode {
+    /// Fail with an error on overflow
+    Checked,
+    /// Fall back to arbitrary precision
+    Big,
+}
 
-    info!("{:?}", result);
+impl FromStr for NumericMode {
+    type Err = anyhow::Error;
 
-    Ok(())
+    fn from_str(s: &str) -> Result<NumericMode> {
+        match s {
+            "checked" => Ok(NumericMode::Checked),
+            "big" => Ok(NumericMode::Big),
+            _ => Err(anyhow::Error::msg(format!(
+                "Unrecognized numeric mode {}",
+                s
+            ))),
+        }
+    }
 }
 
-const ONE_BIT_MASK: u64 = 0b1;
-const THREE_BIT_MASK: u64 = 0b111;
-const FOUR_BIT_MASK: u64 = 0b1111;
+#[derive(Debug)]
+pub struct Day16 {
+    numeric: NumericMode,
+}
 
-fn hex_to_binary(hex: char) -> u64 {
-    match hex {
-        '0' => 0,
-        '1' => 1,
-        '2' => 2,
-        '3' => 3,
-        '4' => 4,
-        '5' => 5,
-        '6' => 6,
-        '7' => 7,
-        '8' => 8,
-        '9' => 9,
-        'A' => 10,
-        'B' => 11,
-        'C' => 12,
-        'D' => 13,
-        'E' => 14,
-        'F' => 15,
-        _ => unreachable!("This should be unreachable!"),
+impl Day16 {
+    pub fn new(numeric: NumericMode) -> Day16 {
+        Day16 { numeric }
     }
 }
 
-#[derive(Debug)]
-enum LengthTypeId {
+impl Default for Day16 {
+    fn default() -> Day16 {
+        Day16::new(NumericMode::Checked)
+    }
+}
+
+impl Solver for Day16 {
+    type Input = Packet;
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let name = input.name().to_owned();
+        decode_reader(input.into_reader()).with_context(|| format!("Unable to decode {}", name))
+    }
+
+    fn part1(&self, packet: Self::Input) -> Result<Answer> {
+        part1(packet).map(Answer::from)
+    }
+
+    fn part2(&self, packet: Self::Input) -> Result<Answer> {
+        match self.numeric {
+            NumericMode::Checked => part2(packet).map(Answer::from),
+            NumericMode::Big => packet.big_value().map(Answer::from),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::only_part1("8A004A801A8002F478\n", 16u64),
+            Example::only_part1("620080001611562C8802118E34\n", 12u64),
+            Example::only_part1("C0015000016115A2E0802F182340\n", 23u64),
+            Example::only_part1("A0016C880162017C3686B18A3D4780\n", 31u64),
+            Example::only_part2("C200B40A82\n", 3u64),
+            Example::only_part2("04005AC33890\n", 54u64),
+            Example::only_part2("880086C3E88112\n", 7u64),
+            Example::only_part2("CE00C43D881120\n", 9u64),
+            Example::only_part2("D8005AC2A8F0\n", 1u64),
+            Example::only_part2("F600BC2D8F\n", 0u64),
+            Example::only_part2("9C005AC2F8F0\n", 0u64),
+            Example::only_part2("9C0141080250320F1802104A08\n", 1u64),
+        ]
+    }
+}
+
+const VERSION_BITS: usize = 3;
+const TYPE_ID_BITS: usize = 3;
+const LITERAL_GROUP_BITS: usize = 4;
+const TOTAL_LENGTH_BITS: usize = 15;
+const NUM_PACKETS_BITS: usize = 11;
+/// How deeply packets may be nested inside operators. Decoding, evaluating and dropping packets all
+/// recurse once per level, so without a limit a long enough chain of single sub-packets would
+/// overflow the stack instead of failing with an error.
+pub const MAX_DEPTH: usize = 256;
+
+/// How an operator packet says where its sub-packets end.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LengthTypeId {
+    /// The total length in bits of the sub-packets
     Bits,
+    /// The number of sub-packets
     Packets,
 }
 
@@ -66,118 +120,332 @@ impl LengthTypeId {
     }
 }
 
-#[derive(Debug)]
-enum TypeId {
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TypeId {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
     Literal,
-    Operator,
+    GreaterThan,
+    LessThan,
+    EqualTo,
 }
 
 impl TypeId {
     fn from_number(num: u64) -> TypeId {
         match num {
+            0 => TypeId::Sum,
+            1 => TypeId::Product,
+            2 => TypeId::Minimum,
+            3 => TypeId::Maximum,
             4 => TypeId::Literal,
-            0 | 1 | 2 | 3 | 5 | 6 | 7 => TypeId::Operator,
+            5 => TypeId::GreaterThan,
+            6 => TypeId::LessThan,
+            7 => TypeId::EqualTo,
             _ => unreachable!("Illegal bits for Type Id"),
         }
     }
+
+    pub fn number(&self) -> u64 {
+        match self {
+            TypeId::Sum => 0,
+            TypeId::Product => 1,
+            TypeId::Minimum => 2,
+            TypeId::Maximum => 3,
+            TypeId::Literal => 4,
+            TypeId::GreaterThan => 5,
+            TypeId::LessThan => 6,
+            TypeId::EqualTo => 7,
+        }
+    }
 }
 
-#[derive(Debug)]
-struct Header {
-    version: u64,
-    type_id: TypeId,
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Header {
+    pub version: u64,
+    pub type_id: TypeId,
 }
 
-fn number_from_bits<T1, T2>(bits: &BitVec<T1, T2>, start: usize, num_bits: usize) -> u64
-where
-    T1: bitvec::order::BitOrder,
-    T2: bitvec::prelude::BitStore,
-{
-    let mut result = 0;
-    for i in start..num_bits {
-        result = result << 1;
-        result = result | bool_to_bit(bits[i])
+impl Header {
+    fn read<R>(reader: &mut BitReader<R>) -> Result<Header>
+    where
+        R: BufRead,
+    {
+        let version = reader.read_bits(VERSION_BITS)?;
+        let type_id = TypeId::from_number(reader.read_bits(TYPE_ID_BITS)?);
+        Ok(Header { version, type_id })
     }
-    result
 }
 
-impl Header {
-    fn from_bits<T1, T2>(bits: &BitVec<T1, T2>, start: usize) -> Header
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Contents {
+    Literal(u64),
+    /// A literal too big for a `u64`. Only used when the value doesn't fit in `Literal`.
+    BigLiteral(BigUint),
+    Operator {
+        length_type_id: LengthTypeId,
+        packets: Vec<Packet>,
+    },
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Packet {
+    pub header: Header,
+    pub contents: Contents,
+}
+
+impl Packet {
+    /// The version of this packet plus the versions of all the packets inside it.
+    pub fn version_sum(&self) -> u64 {
+        let sub_packets = match &self.contents {
+            Contents::Literal(_) | Contents::BigLiteral(_) => 0,
+            Contents::Operator { packets, .. } => packets.iter().map(Packet::version_sum).sum(),
+        };
+        self.header.version + sub_packets
+    }
+
+    /// Evaluates the expression this packet represents, failing if any part of it overflows.
+    pub fn value(&self) -> Result<u64> {
+        self.evaluate()
+    }
+
+    /// Evaluates the expression this packet represents with arbitrary precision.
+    pub fn big_value(&self) -> Result<BigUint> {
+        self.evaluate()
+    }
+
+    fn evaluate<V>(&self) -> Result<V>
     where
-        T1: bitvec::order::BitOrder,
-        T2: bitvec::prelude::BitStore,
+        V: Value,
     {
-        let version = number_from_bits(bits, start, start + 3);
-        let type_id = number_from_bits(bits, start + 3, start + 6);
-        let type_id = TypeId::from_number(type_id);
-        Header { version, type_id }
+        let packets = match &self.contents {
+            Contents::Literal(value) => return Ok(V::from_u64(*value)),
+            Contents::BigLiteral(value) => return V::from_big(value),
+            Contents::Operator { packets, .. } => packets,
+        };
+        let values = packets
+            .iter()
+            .map(Packet::evaluate)
+            .collect::<Result<Vec<V>>>()?;
+
+        match self.header.type_id {
+            TypeId::Sum => values
+                .iter()
+                .try_fold(V::from_u64(0), |sum, value| sum.add(value)),
+            TypeId::Product => values
+                .iter()
+                .try_fold(V::from_u64(1), |product, value| product.mul(value)),
+            TypeId::Minimum => values
+                .into_iter()
+                .min()
+                .ok_or_else(|| anyhow::Error::msg("Minimum packet has no sub-packets")),
+            TypeId::Maximum => values
+                .into_iter()
+                .max()
+                .ok_or_else(|| anyhow::Error::msg("Maximum packet has no sub-packets")),
+            TypeId::GreaterThan => compare(&values, |a, b| a > b),
+            TypeId::LessThan => compare(&values, |a, b| a < b),
+            TypeId::EqualTo => compare(&values, |a, b| a == b),
+            TypeId::Literal => Err(anyhow::Error::msg("Literal packet has sub-packets")),
+        }
     }
 }
 
-#[derive(Debug)]
-struct Packet {
-    header: Header,
+/// What packets can be evaluated as.
+trait Value: Ord + Sized {
+    fn from_u64(value: u64) -> Self;
+    fn from_big(value: &BigUint) -> Result<Self>;
+    fn add(&self, other: &Self) -> Result<Self>;
+    fn mul(&self, other: &Self) -> Result<Self>;
 }
 
-fn bit_to_bool(b: u64) -> bool {
-    match b {
-        0 => false,
-        1 => true,
-        _ => unreachable!("Invalid bit to convert to bool: {}", b),
+impl Value for u64 {
+    fn from_u64(value: u64) -> u64 {
+        value
+    }
+
+    fn from_big(value: &BigUint) -> Result<u64> {
+        u64::try_from(value)
+            .map_err(|_| anyhow::Error::msg(format!("Literal {} doesn't fit in 64 bits", value)))
+    }
+
+    fn add(&self, other: &u64) -> Result<u64> {
+        self.checked_add(*other)
+            .ok_or_else(|| anyhow::Error::msg(format!("{} + {} overflows 64 bits", self, other)))
+    }
+
+    fn mul(&self, other: &u64) -> Result<u64> {
+        self.checked_mul(*other)
+            .ok_or_else(|| anyhow::Error::msg(format!("{} * {} overflows 64 bits", self, other)))
     }
 }
 
-fn bool_to_bit(b: bool) -> u64 {
-    match b {
-        true => 1,
-        false => 0,
+impl Value for BigUint {
+    fn from_u64(value: u64) -> BigUint {
+        BigUint::from(value)
+    }
+
+    fn from_big(value: &BigUint) -> Result<BigUint> {
+        Ok(value.clone())
+    }
+
+    fn add(&self, other: &BigUint) -> Result<BigUint> {
+        Ok(self + other)
+    }
+
+    fn mul(&self, other: &BigUint) -> Result<BigUint> {
+        Ok(self * other)
     }
 }
 
-fn hex_to_bitvec(chars: &[char]) -> BitVec<Msb0, u64> {
-    chars
-        .into_iter()
-        .map(|c| hex_to_binary(*c))
-        .fold(bitvec![Msb0, u64;], |mut accum, b| {
-            for i in 0..4 {
-                let val = (b >> (4 - 1 - i)) & ONE_BIT_MASK;
-                let val = bit_to_bool(val);
-                accum.push(val);
-            }
-            accum
-        })
+/// 1 if `op` holds for the two values, 0 otherwise.
+fn compare<V, F>(values: &[V], op: F) -> Result<V>
+where
+    V: Value,
+    F: Fn(&V, &V) -> bool,
+{
+    match values {
+        [a, b] => Ok(V::from_u64(op(a, b) as u64)),
+        _ => Err(anyhow::Error::msg(format!(
+            "Comparison packets need exactly 2 sub-packets but got {}",
+            values.len()
+        ))),
+    }
 }
 
-fn read_packet<T1, T2>(bits: &BitVec<T1, T2>, start: usize) -> Packet
+/// Reads the next packet, appending the offset of it and every packet inside it to `offsets` in
+/// pre-order. `depth` is how many operators the packet is inside.
+fn read_packet<R>(
+    reader: &mut BitReader<R>,
+    offsets: &mut Vec<usize>,
+    depth: usize,
+) -> Result<Packet>
 where
-    T1: bitvec::order::BitOrder,
-    T2: bitvec::prelude::BitStore,
+    R: BufRead,
 {
-    let header = Header::from_bits(bits, start);
+    if depth > MAX_DEPTH {
+        return Err(anyhow::Error::msg(format!(
+            "Packet at bit {} is nested more than {} operators deep",
+            reader.position(),
+            MAX_DEPTH
+        )));
+    }
+    offsets.push(reader.position());
+    let header = Header::read(reader)?;
+
+    let contents = match header.type_id {
+        TypeId::Literal => read_literal(reader)?,
+        _ => read_operator(reader, offsets, depth)?,
+    };
 
-    Packet { header }
+    Ok(Packet { header, contents })
 }
 
-fn part1(chars: Vec<char>) -> Result<usize> {
-    let bits: BitVec<Msb0, u64> = hex_to_bitvec(&chars);
+/// A literal is groups of 5 bits, each a flag saying whether another group follows and then 4 bits
+/// of the value. Switches to a `BigLiteral` once the value no longer fits in a `u64`.
+fn read_literal<R>(reader: &mut BitReader<R>) -> Result<Contents>
+where
+    R: BufRead,
+{
+    let mut value = 0;
+    let mut big_value: Option<BigUint> = None;
+    loop {
+        let more = reader.read_bool()?;
+        let group = reader.read_bits(LITERAL_GROUP_BITS)?;
+        let fits = value >> (u64::BITS as usize - LITERAL_GROUP_BITS) == 0;
+        if big_value.is_none() && fits {
+            value = (value << LITERAL_GROUP_BITS) | group;
+        } else {
+            let big = big_value.unwrap_or_else(|| BigUint::from(value));
+            big_value = Some(&(&big << LITERAL_GROUP_BITS) + &BigUint::from(group));
+        }
+        if !more {
+            return Ok(match big_value {
+                Some(big) => Contents::BigLiteral(big),
+                None => Contents::Literal(value),
+            });
+        }
+    }
+}
+
+fn read_operator<R>(
+    reader: &mut BitReader<R>,
+    offsets: &mut Vec<usize>,
+    depth: usize,
+) -> Result<Contents>
+where
+    R: BufRead,
+{
+    let length_type_id = LengthTypeId::from_bit(reader.read_bits(1)?);
+    let mut packets = Vec::new();
+
+    match length_type_id {
+        LengthTypeId::Bits => {
+            let length = reader.read_bits(TOTAL_LENGTH_BITS)? as usize;
+            let start = reader.position();
+            let end = start + length;
+            while reader.position() < end {
+                packets.push(read_packet(reader, offsets, depth + 1)?);
+            }
+            if reader.position() != end {
+                return Err(anyhow::Error::msg(format!(
+                    "Sub-packets starting at bit {} should be {} bits long but were {}",
+                    start,
+                    length,
+                    reader.position() - start
+                )));
+            }
+        }
+        LengthTypeId::Packets => {
+            let num_packets = reader.read_bits(NUM_PACKETS_BITS)?;
+            for _ in 0..num_packets {
+                packets.push(read_packet(reader, offsets, depth + 1)?);
+            }
+        }
+    }
 
-    let packet = read_packet(&bits, 0);
-    info!("{:?}", packet);
+    Ok(Contents::Operator {
+        length_type_id,
+        packets,
+    })
+}
 
-    Ok(0)
+/// Decodes the outermost packet of a hex transmission. Anything after it is padding.
+pub fn decode(hex: &str) -> Result<Packet> {
+    decode_reader(hex.as_bytes())
 }
 
-fn part2(chars: Vec<char>) -> Result<usize> {
-    Ok(0)
+/// Like `decode`, but also returns the bit offset where each packet starts, in pre-order.
+pub fn decode_with_offsets(hex: &str) -> Result<(Packet, Vec<usize>)> {
+    decode_reader_with_offsets(hex.as_bytes())
 }
 
-fn print_bitvec<T1, T2>(bits: &BitVec<T1, T2>)
+/// Decodes the outermost packet of a hex transmission as it's read. Nothing after the packet is
+/// read.
+pub fn decode_reader<R>(reader: R) -> Result<Packet>
 where
-    T1: bitvec::order::BitOrder,
-    T2: bitvec::prelude::BitStore,
+    R: BufRead,
 {
-    for b in bits {
-        print!("{}", if b == true { 1 } else { 0 })
-    }
-    println!();
+    decode_reader_with_offsets(reader).map(|(packet, _)| packet)
+}
+
+pub fn decode_reader_with_offsets<R>(reader: R) -> Result<(Packet, Vec<usize>)>
+where
+    R: BufRead,
+{
+    let mut reader = BitReader::new(reader);
+    let mut offsets = Vec::new();
+    let packet = read_packet(&mut reader, &mut offsets, 0)?;
+    debug!("{:?}", packet);
+
+    Ok((packet, offsets))
+}
+
+fn part1(packet: Packet) -> Result<u64> {
+    Ok(packet.version_sum())
+}
+
+fn part2(packet: Packet) -> Result<u64> {
+    packet.value()
 }
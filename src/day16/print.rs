@@ -0,0 +1,93 @@
+use super::{decode_with_offsets, Contents, LengthTypeId, Packet, TypeId};
+use anyhow::Result;
+use std::fmt::Write as _;
+
+fn operator_name(type_id: TypeId) -> &'static str {
+    match type_id {
+        TypeId::Sum => "sum",
+        TypeId::Product => "product",
+        TypeId::Minimum => "min",
+        TypeId::Maximum => "max",
+        TypeId::Literal => "literal",
+        TypeId::GreaterThan => "gt",
+        TypeId::LessThan => "lt",
+        TypeId::EqualTo => "eq",
+    }
+}
+
+/// The packet as an expression, e.g. `sum(3, max(7, 9))`.
+pub fn infix(packet: &Packet) -> String {
+    match &packet.contents {
+        Contents::Literal(value) => value.to_string(),
+        Contents::BigLiteral(value) => value.to_string(),
+        Contents::Operator { packets, .. } => format!(
+            "{}({})",
+            operator_name(packet.header.type_id),
+            packets
+                .iter()
+                .map(infix)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// One line per packet, indented by depth. `offsets` are the bit offsets of the packets in
+/// pre-order, as returned by `decode_with_offsets`.
+pub fn tree(packet: &Packet, offsets: &[usize]) -> String {
+    let mut s = String::new();
+    write_tree(&mut s, packet, &mut offsets.iter(), 0);
+    s
+}
+
+fn write_tree<'a, I>(s: &mut String, packet: &Packet, offsets: &mut I, depth: usize)
+where
+    I: Iterator<Item = &'a usize>,
+{
+    let offset = offsets
+        .next()
+        .map_or_else(|| "?".to_owned(), |offset| offset.to_string());
+    let label = match &packet.contents {
+        Contents::Literal(value) => value.to_string(),
+        Contents::BigLiteral(value) => value.to_string(),
+        Contents::Operator { length_type_id, .. } => {
+            let length_type = match length_type_id {
+                LengthTypeId::Bits => "bits",
+                LengthTypeId::Packets => "packets",
+            };
+            format!("{} [{}]", operator_name(packet.header.type_id), length_type)
+        }
+    };
+    writeln!(
+        s,
+        "{}{} (version {}, bit {})",
+        "  ".repeat(depth),
+        label,
+        packet.header.version,
+        offset
+    )
+    .unwrap();
+
+    if let Contents::Operator { packets, .. } = &packet.contents {
+        for sub_packet in packets {
+            write_tree(s, sub_packet, offsets, depth + 1);
+        }
+    }
+}
+
+/// Decodes a hex transmission and describes it: its version sum, value, expression and tree.
+pub fn explain(hex: &str) -> Result<String> {
+    let (packet, offsets) = decode_with_offsets(hex)?;
+
+    let value = match packet.big_value() {
+        Ok(value) => value.to_string(),
+        Err(e) => format!("error: {:#}", e),
+    };
+    Ok(format!(
+        "version sum: {}\nvalue: {}\ninfix: {}\n{}",
+        packet.version_sum(),
+        value,
+        infix(&packet),
+        tree(&packet, &offsets)
+    ))
+}
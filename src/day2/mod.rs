@@ -1,9 +1,9 @@
-use crate::arguments::Arguments;
+use crate::common::parse::{InputLine, ParseError};
 use crate::day2::Direction::{Down, Forward, Up};
-use crate::io::reader;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::io::BufRead;
 
 #[derive(Debug)]
 enum Direction {
@@ -13,71 +13,76 @@ enum Direction {
 }
 
 impl Direction {
-    fn from_str(s: &str) -> anyhow::Result<Direction> {
+    fn from_str(line: &InputLine, s: &str) -> Result<Direction, ParseError> {
         match s {
             "forward" => Ok(Forward),
             "up" => Ok(Up),
             "down" => Ok(Down),
-            _ => Err(anyhow::Error::msg(format!("Unrecognized direction {}", s))),
+            _ => Err(line.error(s, format!("Unrecognized direction {}", s))),
         }
     }
 }
 
 #[derive(Debug)]
-struct Line {
+pub struct Line {
     direction: Direction,
     amount: i64,
 }
 
 impl Line {
-    fn from_str(s: &str) -> anyhow::Result<Line> {
+    fn from_input(line: &InputLine) -> Result<Line, ParseError> {
         lazy_static! {
             static ref RE: Regex = Regex::new(r"(\w+) (\d+)").unwrap();
         }
-        // let RE = Regex::new(r"(\w+) (\d+)").unwrap();
 
         let capture = RE
-            .captures(s)
-            .ok_or(anyhow::Error::msg(format!("Unable to parse line: {}", s)))?;
-
-        let line = Line {
-            direction: Direction::from_str(
-                capture
-                    .get(1)
-                    .ok_or(anyhow::Error::msg(format!("Missing direction")))?
-                    .as_str(),
-            )?,
-            amount: capture
-                .get(2)
-                .ok_or(anyhow::Error::msg(format!("Missing amount")))?
-                .as_str()
-                .parse()?,
-        };
-
-        Ok(line)
+            .captures(&line.text)
+            .ok_or_else(|| line.error(&line.text, "Expected '<direction> <amount>'"))?;
+
+        // Both groups always participate in a match
+        Ok(Line {
+            direction: Direction::from_str(line, capture.get(1).unwrap().as_str())?,
+            amount: line.parse(capture.get(2).unwrap().as_str())?,
+        })
     }
 }
 
-pub fn day2(args: &Arguments) -> anyhow::Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
+const EXAMPLE: &str = "\
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2
+";
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = Vec<Line>;
+
+    fn parse(&self, input: PuzzleInput) -> anyhow::Result<Self::Input> {
+        let mut lines: Vec<Line> = Vec::new();
+        for line in input.lines() {
+            let line = line?;
+            let line = Line::from_input(&line)?;
+            lines.push(line);
+        }
 
-    let mut lines: Vec<Line> = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        let line = Line::from_str(&line)?;
-        lines.push(line);
+        Ok(lines)
     }
-    let lines = lines;
 
-    let result = if args.part == 1 {
-        part1(lines)
-    } else {
-        part2(lines)
-    }?;
+    fn part1(&self, lines: Self::Input) -> anyhow::Result<Answer> {
+        part1(lines).map(Answer::from)
+    }
 
-    info!("{:?}", result);
+    fn part2(&self, lines: Self::Input) -> anyhow::Result<Answer> {
+        part2(lines).map(Answer::from)
+    }
 
-    Ok(())
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 150i64, 900i64)]
+    }
 }
 
 #[derive(Debug, Default)]
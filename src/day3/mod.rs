@@ -1,29 +1,69 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
-use std::io::BufRead;
 
-pub fn day3(args: &Arguments) -> Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
+const EXAMPLE: &str = "\
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+";
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input = Vec<Vec<char>>;
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let mut lines: Vec<Vec<char>> = Vec::new();
+        for line in input.lines() {
+            let line = line?;
+            for (i, c) in line.text.chars().enumerate() {
+                if c != '0' && c != '1' {
+                    return Err(line
+                        .error_at(i + 1, format!("Expected a bit but got '{}'", c))
+                        .into());
+                }
+            }
+            let bits = line.text.chars().count();
+            if let Some(width) = lines.first().map(Vec::len) {
+                if bits != width {
+                    return Err(line
+                        .error_at(
+                            width.min(bits) + 1,
+                            format!(
+                                "Expected {} bits like the first line but got {}",
+                                width, bits
+                            ),
+                        )
+                        .into());
+                }
+            }
+            lines.push(line.text.chars().collect());
+        }
 
-    let mut lines: Vec<Vec<char>> = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        // let line = line.parse()?;
-        let line = line.chars().into_iter().collect();
-        lines.push(line);
+        Ok(lines)
     }
-    let lines = lines;
 
-    let result = if args.part == 1 {
-        part1(lines)
-    } else {
-        part2(lines)
-    }?;
+    fn part1(&self, lines: Self::Input) -> Result<Answer> {
+        part1(lines).map(Answer::from)
+    }
 
-    info!("{:?}", result);
+    fn part2(&self, lines: Self::Input) -> Result<Answer> {
+        part2(lines).map(Answer::from)
+    }
 
-    Ok(())
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 198i64, 230i64)]
+    }
 }
 
 #[derive(Debug, Default)]
@@ -32,14 +72,14 @@ struct BitCount {
     one: i64,
 }
 
-fn count_bits(lines: &Vec<Vec<char>>) -> Result<Vec<BitCount>> {
+fn count_bits(lines: &[Vec<char>]) -> Result<Vec<BitCount>> {
     let size = if let Some(v) = lines.first() {
         v.len()
     } else {
         0
     };
 
-    if size <= 0 {
+    if size == 0 {
         return Err(anyhow::Error::msg("Invalid input!"));
     }
 
@@ -49,8 +89,7 @@ fn count_bits(lines: &Vec<Vec<char>>) -> Result<Vec<BitCount>> {
     }
 
     for line in lines {
-        for i in 0..line.len() {
-            let c = line[i];
+        for (i, c) in line.iter().enumerate() {
             match c {
                 '0' => totals[i].zero += 1,
                 '1' => totals[i].one += 1,
@@ -65,15 +104,14 @@ fn count_bits(lines: &Vec<Vec<char>>) -> Result<Vec<BitCount>> {
 fn part1(lines: Vec<Vec<char>>) -> Result<i64> {
     let totals = count_bits(&lines)?;
 
-    info!("{:?}", totals);
+    debug!("{:?}", totals);
 
     let mut gamma = 0;
     let mut epsilon = 0;
 
-    for i in 0..totals.len() {
-        let count = &totals[i];
-        gamma = gamma << 1;
-        epsilon = epsilon << 1;
+    for count in &totals {
+        gamma <<= 1;
+        epsilon <<= 1;
         if count.one > count.zero {
             gamma |= 1;
         } else {
@@ -81,7 +119,7 @@ fn part1(lines: Vec<Vec<char>>) -> Result<i64> {
         }
     }
 
-    info!("gamma: {}, epsilon: {}", gamma, epsilon);
+    debug!("gamma: {}, epsilon: {}", gamma, epsilon);
 
     Ok(gamma * epsilon)
 }
@@ -90,13 +128,13 @@ fn part2(lines: Vec<Vec<char>>) -> Result<i64> {
     let totals = count_bits(&lines)?;
 
     let mut oxygen = lines.clone();
-    let mut co2 = lines.clone();
+    let mut co2 = lines;
 
     for i in 0..totals.len() {
         let oxygen_totals = count_bits(&oxygen)?;
         let co2_totals = count_bits(&co2)?;
 
-        info!("num oxygen: {}, num co2: {}", oxygen.len(), co2.len());
+        debug!("num oxygen: {}, num co2: {}", oxygen.len(), co2.len());
 
         let most_common = if oxygen_totals[i].one >= oxygen_totals[i].zero {
             '1'
@@ -110,10 +148,10 @@ fn part2(lines: Vec<Vec<char>>) -> Result<i64> {
         };
 
         if oxygen.len() > 1 {
-            oxygen = oxygen.into_iter().filter(|v| v[i] == most_common).collect();
+            oxygen.retain(|v| v[i] == most_common);
         }
         if co2.len() > 1 {
-            co2 = co2.into_iter().filter(|v| v[i] == least_common).collect();
+            co2.retain(|v| v[i] == least_common);
         }
     }
 
@@ -121,17 +159,24 @@ fn part2(lines: Vec<Vec<char>>) -> Result<i64> {
         return Err(anyhow::Error::msg("Invalid input!"));
     }
 
-    let oxygen = chars_to_decimal(oxygen.first().ok_or(anyhow::Error::msg("Invalid input!"))?)?;
-    let co2 = chars_to_decimal(co2.first().ok_or(anyhow::Error::msg("Invalid input!"))?)?;
+    let oxygen = chars_to_decimal(
+        oxygen
+            .first()
+            .ok_or_else(|| anyhow::Error::msg("Invalid input!"))?,
+    )?;
+    let co2 = chars_to_decimal(
+        co2.first()
+            .ok_or_else(|| anyhow::Error::msg("Invalid input!"))?,
+    )?;
 
     Ok(oxygen * co2)
 }
 
-fn chars_to_decimal(chars: &Vec<char>) -> Result<i64> {
+fn chars_to_decimal(chars: &[char]) -> Result<i64> {
     let mut result = 0;
 
     for c in chars {
-        result = result << 1;
+        result <<= 1;
         if *c == '1' {
             result |= 1;
         }
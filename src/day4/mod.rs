@@ -1,39 +1,69 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::common::parse::{InputLine, ParseError};
+use crate::common::point::Position;
+use crate::io::{InputLines, PuzzleInput};
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
 use std::collections::HashMap;
-use std::io::BufRead;
 
-pub fn day4(args: &Arguments) -> Result<()> {
-    let mut reader = reader(args.src_file.as_ref())?;
-
-    let mut first_line = String::new();
-    reader.read_line(&mut first_line)?;
-    let numbers: Vec<usize> = first_line
-        .split(",")
-        .map(|s| s.trim().parse().unwrap())
-        .collect();
-
-    let mut empty_line = String::new();
-    reader.read_line(&mut empty_line)?;
+const EXAMPLE: &str = "\
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Input = (Vec<usize>, Vec<Board>);
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let mut lines = input.lines();
+
+        let first_line = lines.expect_line()?;
+        let numbers = first_line
+            .text
+            .split(',')
+            .map(|s| first_line.parse(s.trim()))
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+
+        let mut boards: Vec<Board> = Vec::new();
+        while let Some(board) = Board::new(&mut lines)? {
+            boards.push(board);
+        }
 
-    let mut boards: Vec<Board> = Vec::new();
-    while let Ok(board) = Board::new(&mut reader) {
-        boards.push(board);
+        Ok((numbers, boards))
     }
 
-    let result = if args.part == 1 {
-        part1(&numbers, &mut boards)
-    } else {
-        part2(&numbers, &mut boards)
-    }?;
+    fn part1(&self, (numbers, mut boards): Self::Input) -> Result<Answer> {
+        part1(&numbers, &mut boards).map(Answer::from)
+    }
 
-    info!("{:?}", result);
+    fn part2(&self, (numbers, mut boards): Self::Input) -> Result<Answer> {
+        part2(&numbers, &mut boards).map(Answer::from)
+    }
 
-    Ok(())
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 4512usize, 1924usize)]
+    }
 }
 
-fn part1(numbers: &[usize], boards: &mut Vec<Board>) -> Result<usize> {
+fn part1(numbers: &[usize], boards: &mut [Board]) -> Result<usize> {
     for number in numbers {
         for board in &mut *boards {
             if board.mark(*number) {
@@ -46,7 +76,7 @@ fn part1(numbers: &[usize], boards: &mut Vec<Board>) -> Result<usize> {
     Err(anyhow::Error::msg("Did not find a winning board"))
 }
 
-fn part2(numbers: &[usize], boards: &mut Vec<Board>) -> Result<usize> {
+fn part2(numbers: &[usize], boards: &mut [Board]) -> Result<usize> {
     let mut last_number: Option<usize> = None;
     let mut last_board: Option<usize> = None;
 
@@ -70,7 +100,7 @@ fn part2(numbers: &[usize], boards: &mut Vec<Board>) -> Result<usize> {
     let last_number = last_number.unwrap();
     let last_board = last_board.unwrap();
 
-    info!(
+    debug!(
         "num: {}, board: {:?}",
         last_number,
         boards.get(last_board).unwrap()
@@ -81,27 +111,47 @@ fn part2(numbers: &[usize], boards: &mut Vec<Board>) -> Result<usize> {
 }
 
 #[derive(Debug, Default)]
-struct Board {
+pub struct Board {
     rows: Vec<Row>,
     positions: HashMap<usize, Position>,
     won: bool,
 }
 
 impl Board {
-    fn new(reader: &mut Box<dyn BufRead>) -> Result<Board> {
+    /// Reads the next board, skipping any empty lines before it. Returns `None` once there are no
+    /// boards left.
+    fn new(lines: &mut InputLines) -> Result<Option<Board>> {
         let mut rows = Vec::new();
 
-        let mut line = String::new();
-        while let Ok(count) = reader.read_line(&mut line) {
-            if count <= 0 {
-                return Err(anyhow::Error::msg("done"));
-            }
-            if line.trim().len() <= 0 {
+        for line in lines {
+            let line = line?;
+            if line.text.trim().is_empty() {
+                if rows.is_empty() {
+                    continue;
+                }
                 break;
             }
-            let row = Row::new(&line);
+            let row = Row::new(&line)?;
+            if let Some(width) = rows.first().map(|row: &Row| row.columns.len()) {
+                if row.columns.len() != width {
+                    let message = format!(
+                        "Expected {} numbers like the first row of the board but got {}",
+                        width,
+                        row.columns.len()
+                    );
+                    // point at the first extra number, or the end of a short row
+                    let error = match line.text.split_whitespace().nth(width) {
+                        Some(extra) => line.error(extra, message),
+                        None => line.error_at(line.text.chars().count() + 1, message),
+                    };
+                    return Err(error.into());
+                }
+            }
             rows.push(row);
-            line.clear()
+        }
+
+        if rows.is_empty() {
+            return Ok(None);
         }
 
         let positions = build_positions(&rows);
@@ -112,7 +162,7 @@ impl Board {
             ..Default::default()
         };
 
-        Ok(board)
+        Ok(Some(board))
     }
 
     /// Returns true if marking the number causes this board to win
@@ -131,7 +181,7 @@ impl Board {
         let won = self.check_row(position.y) || self.check_column(position.x);
         self.won = self.won || won;
 
-        return self.won;
+        self.won
     }
 
     fn check_row(&self, y: usize) -> bool {
@@ -155,16 +205,15 @@ impl Board {
             .map(|row| &row.columns)
             .flat_map(|x| x.iter())
             .filter(|square| !square.marked)
-            .fold(0, |accum, square| accum + square.number)
+            .map(|square| square.number)
+            .sum()
     }
 }
 
 fn build_positions(rows: &[Row]) -> HashMap<usize, Position> {
     let mut positions = HashMap::new();
-    for y in 0..rows.len() {
-        let row = &rows[y];
-        for x in 0..row.columns.len() {
-            let square = &row.columns[x];
+    for (y, row) in rows.iter().enumerate() {
+        for (x, square) in row.columns.iter().enumerate() {
             positions.insert(square.number, Position::new(x, y));
         }
     }
@@ -172,35 +221,20 @@ fn build_positions(rows: &[Row]) -> HashMap<usize, Position> {
     positions
 }
 
-#[derive(Debug, Default)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-impl Position {
-    fn new(x: usize, y: usize) -> Position {
-        Position { x, y }
-    }
-}
-
 #[derive(Debug, Default)]
 struct Row {
     columns: Vec<Square>,
 }
 
 impl Row {
-    fn new(line: &str) -> Row {
+    fn new(line: &InputLine) -> Result<Row, ParseError> {
         let columns = line
+            .text
             .split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .map(|num| Square::new(num))
-            .collect();
+            .map(|s| line.parse(s).map(Square::new))
+            .collect::<Result<Vec<Square>, ParseError>>()?;
 
-        Row {
-            columns,
-            ..Default::default()
-        }
+        Ok(Row { columns })
     }
 
     fn check(&self) -> bool {
@@ -1,108 +1,78 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::common::parse::{InputLine, ParseError};
+use crate::common::point::Point2;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
-use std::cmp::{max, min};
 use std::collections::HashMap;
-use std::io::BufRead;
 
-pub fn day5(args: &Arguments) -> Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
+const EXAMPLE: &str = "\
+0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2
+";
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Input = Vec<LineSegment>;
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let mut lines: Vec<LineSegment> = Vec::new();
+        for line in input.lines() {
+            let line = line?;
+            let line = LineSegment::from_input(&line)?;
+            lines.push(line);
+        }
 
-    let mut lines: Vec<LineSegment> = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        let line = LineSegment::from_str(&line);
-        lines.push(line);
+        Ok(lines)
     }
-    let lines = lines;
-
-    let result = if args.part == 1 {
-        part1(lines)
-    } else {
-        part2(lines)
-    }?;
-
-    info!("{}", result);
-
-    Ok(())
-}
-
-#[derive(Debug, Default, Eq, PartialEq, Hash)]
-struct Coordinate {
-    x: u32,
-    y: u32,
-}
 
-impl Coordinate {
-    fn new(x: u32, y: u32) -> Coordinate {
-        Coordinate { x, y }
+    fn part1(&self, lines: Self::Input) -> Result<Answer> {
+        part1(lines).map(Answer::from)
     }
 
-    fn from_str(s: &str) -> Coordinate {
-        let nums: Vec<u32> = s.split(",").map(|x| x.trim().parse().unwrap()).collect();
+    fn part2(&self, lines: Self::Input) -> Result<Answer> {
+        part2(lines).map(Answer::from)
+    }
 
-        Coordinate {
-            x: *nums.get(0).unwrap(),
-            y: *nums.get(1).unwrap(),
-        }
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 5usize, 12usize)]
     }
 }
 
+type Coordinate = Point2<i32>;
+
 #[derive(Debug, Default)]
-struct LineSegment {
+pub struct LineSegment {
     start: Coordinate,
     end: Coordinate,
 }
 
 impl LineSegment {
-    fn from_str(line: &str) -> LineSegment {
-        let mut coords: Vec<Coordinate> = line
-            .split("->")
-            .into_iter()
-            .map(|x| x.trim())
-            .map(|x| Coordinate::from_str(x))
-            .collect();
-
-        LineSegment {
-            start: coords.remove(0),
-            end: coords.remove(0),
-        }
+    fn from_input(line: &InputLine) -> Result<LineSegment, ParseError> {
+        let (start, end) = line.split_once(&line.text, "->")?;
+
+        Ok(LineSegment {
+            start: Coordinate::from_input(line, start.trim())?,
+            end: Coordinate::from_input(line, end.trim())?,
+        })
     }
 
     // There's probably a better way to do this, but we'll just brute force the solution for now
     fn all_coordinates(&self) -> Vec<Coordinate> {
-        let mut coords = Vec::new();
-        if self.start.x == self.end.x {
-            // vertical
-            for y in min(self.start.y, self.end.y)..(max(self.start.y, self.end.y) + 1) {
-                coords.push(Coordinate::new(self.start.x, y));
-            }
-        } else if self.start.y == self.end.y {
-            // horizontal
-            for x in min(self.start.x, self.end.x)..(max(self.start.x, self.end.x) + 1) {
-                coords.push(Coordinate::new(x, self.start.y));
-            }
-        } else {
-            // diagonal
-            let x_increase = self.start.x < self.end.x;
-            let y_increase = self.start.y < self.end.y;
-
-            for i in 0..((max(self.start.x, self.end.x) - min(self.start.x, self.end.x)) + 1) {
-                let x = if x_increase {
-                    self.start.x + i
-                } else {
-                    self.start.x - i
-                };
-                let y = if y_increase {
-                    self.start.y + i
-                } else {
-                    self.start.y - i
-                };
-
-                coords.push(Coordinate::new(x, y));
-            }
-        }
-        coords
+        // lines are only ever vertical, horizontal or at 45 degrees, so this is the step between
+        // each point on the line and the chebyshev distance is the number of steps
+        let step = (self.end - self.start).signum();
+        (0..=self.start.chebyshev_distance(self.end))
+            .map(|i| self.start + step * i)
+            .collect()
     }
 }
 
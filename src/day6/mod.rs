@@ -1,25 +1,46 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::common::parse::ParseError;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
-use std::io::BufRead;
 
-pub fn day6(args: &Arguments) -> Result<()> {
-    let mut reader = reader(args.src_file.as_ref())?;
+const EXAMPLE: &str = "\
+3,4,3,1,2
+";
 
-    let mut line = String::new();
-    reader.read_line(&mut line)?;
+pub struct Day6;
 
-    let numbers = line.split(",").map(|s| s.trim().parse().unwrap()).collect();
+impl Solver for Day6 {
+    type Input = Vec<usize>;
 
-    let result = if args.part == 1 {
-        simulate_fish(numbers, 80)
-    } else {
-        simulate_fish(numbers, 256)
-    }?;
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let line = input.lines().expect_line()?;
 
-    info!("{:?}", result);
+        let numbers = line
+            .text
+            .split(',')
+            .map(|s| {
+                let timer = line.parse(s.trim())?;
+                if timer > INITIAL_COUNT {
+                    return Err(line.error(s.trim(), "Timer is too large"));
+                }
+                Ok(timer)
+            })
+            .collect::<Result<Self::Input, ParseError>>()?;
 
-    Ok(())
+        Ok(numbers)
+    }
+
+    fn part1(&self, numbers: Self::Input) -> Result<Answer> {
+        simulate_fish(numbers, 80).map(Answer::from)
+    }
+
+    fn part2(&self, numbers: Self::Input) -> Result<Answer> {
+        simulate_fish(numbers, 256).map(Answer::from)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 5934u64, 26984457539u64)]
+    }
 }
 
 const RESET_COUNT: usize = 6;
@@ -32,11 +53,7 @@ fn simulate_day(counts: &mut Vec<u64>) {
 }
 
 fn simulate_fish(numbers: Vec<usize>, num_days: usize) -> Result<u64> {
-    let mut counts: Vec<u64> = Vec::new();
-
-    for _ in 0..INITIAL_COUNT + 1 {
-        counts.push(0);
-    }
+    let mut counts: Vec<u64> = vec![0; INITIAL_COUNT + 1];
 
     for num in numbers {
         counts[num] += 1;
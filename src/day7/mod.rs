@@ -1,29 +1,45 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::common::parse::ParseError;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
 use std::cmp::{max, min};
-use std::io::BufRead;
 
-pub fn day7(args: &Arguments) -> Result<()> {
-    let mut reader = reader(args.src_file.as_ref())?;
+const EXAMPLE: &str = "\
+16,1,2,0,4,2,7,1,2,14
+";
 
-    let mut line = String::new();
-    reader.read_line(&mut line)?;
+pub struct Day7;
 
-    let numbers = line.split(",").map(|s| s.trim().parse().unwrap()).collect();
+impl Solver for Day7 {
+    type Input = Vec<u64>;
 
-    let result = if args.part == 1 {
-        min_fuel(numbers, |a, b| max(a, b) - min(a, b))
-    } else {
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let line = input.lines().expect_line()?;
+
+        let numbers = line
+            .text
+            .split(',')
+            .map(|s| line.parse(s.trim()))
+            .collect::<Result<Self::Input, ParseError>>()?;
+
+        Ok(numbers)
+    }
+
+    fn part1(&self, numbers: Self::Input) -> Result<Answer> {
+        min_fuel(numbers, |a, b| max(a, b) - min(a, b)).map(Answer::from)
+    }
+
+    fn part2(&self, numbers: Self::Input) -> Result<Answer> {
         min_fuel(numbers, |a, b| {
             let distance = max(a, b) - min(a, b);
             (distance * (distance + 1)) / 2
         })
-    }?;
-
-    info!("{:?}", result);
+        .map(Answer::from)
+    }
 
-    Ok(())
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 37u64, 168u64)]
+    }
 }
 
 fn min_fuel<F>(numbers: Vec<u64>, fuel_calculator: F) -> Result<u64>
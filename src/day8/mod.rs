@@ -1,46 +1,64 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::common::parse::{InputLine, ParseError};
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
-use std::io::BufRead;
 
-pub fn day8(args: &Arguments) -> Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
-
-    let mut lines = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        let line = get_digits_for_line(&line)?;
-        lines.push(line);
+const EXAMPLE: &str = "\
+be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
+";
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        let mut lines = Vec::new();
+        for line in input.lines() {
+            let line = get_digits_for_line(&line?)?;
+            lines.push(line);
+        }
+
+        Ok(lines)
     }
-    let lines = lines;
 
-    let result = if args.part == 1 {
-        part1(lines)
-    } else {
-        part2(lines)
-    }?;
+    fn part1(&self, lines: Self::Input) -> Result<Answer> {
+        part1(lines).map(Answer::from)
+    }
 
-    info!("{:?}", result);
+    fn part2(&self, lines: Self::Input) -> Result<Answer> {
+        part2(lines).map(Answer::from)
+    }
 
-    Ok(())
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 26usize, 61229usize)]
+    }
 }
 
-fn get_easy_code(line: &[String], length: usize) -> String {
-    line.iter()
-        .filter(|s| s.len() == length)
-        .map(|s| s.to_owned())
-        .collect::<Vec<String>>()
-        .first()
-        .unwrap()
-        .to_owned()
+fn get_easy_code(line: &[String], length: usize) -> Option<String> {
+    line.iter().find(|s| s.len() == length).cloned()
 }
 
-fn get_ambiguous_code(line: &[String], length: usize, sub_code: &str, invert: bool) -> String {
+fn get_ambiguous_code(
+    line: &[String],
+    length: usize,
+    sub_code: &str,
+    invert: bool,
+) -> Option<String> {
     line.iter()
         .filter(|s| s.len() == length)
-        .filter(|s| {
+        .find(|s| {
             let b = sub_code.chars().all(|c| s.contains(c));
             if invert {
                 !b
@@ -48,20 +66,16 @@ fn get_ambiguous_code(line: &[String], length: usize, sub_code: &str, invert: bo
                 b
             }
         })
-        .map(|s| s.to_owned())
-        .collect::<Vec<String>>()
-        .first()
-        .unwrap()
-        .to_owned()
+        .cloned()
 }
 
-fn get_codes(left: Vec<String>) -> HashMap<String, char> {
+fn get_codes(left: Vec<String>) -> Option<HashMap<String, char>> {
     let mut codes = HashMap::new();
 
-    let one = get_easy_code(&left, 2);
-    let four = get_easy_code(&left, 4);
-    let seven = get_easy_code(&left, 3);
-    let eight = get_easy_code(&left, 7);
+    let one = get_easy_code(&left, 2)?;
+    let four = get_easy_code(&left, 4)?;
+    let seven = get_easy_code(&left, 3)?;
+    let eight = get_easy_code(&left, 7)?;
 
     let four_remainder = four
         .chars()
@@ -74,10 +88,10 @@ fn get_codes(left: Vec<String>) -> HashMap<String, char> {
         .filter(|c| !four_remainder.contains(*c))
         .collect::<String>();
 
-    let two = get_ambiguous_code(&left, 5, &eight_remainder, false);
-    let three = get_ambiguous_code(&left, 5, &one, false);
-    let five = get_ambiguous_code(&left, 5, &four_remainder, false);
-    let six = get_ambiguous_code(&left, 6, &one, true);
+    let two = get_ambiguous_code(&left, 5, &eight_remainder, false)?;
+    let three = get_ambiguous_code(&left, 5, &one, false)?;
+    let five = get_ambiguous_code(&left, 5, &four_remainder, false)?;
+    let six = get_ambiguous_code(&left, 6, &one, true)?;
 
     codes.insert(one, '1');
     codes.insert(two, '2');
@@ -94,38 +108,56 @@ fn get_codes(left: Vec<String>) -> HashMap<String, char> {
         .map(|s| s.to_owned())
         .collect::<Vec<String>>();
 
-    let nine = get_ambiguous_code(&remaining, 6, &four_remainder, false);
+    let nine = get_ambiguous_code(&remaining, 6, &four_remainder, false)?;
     codes.insert(nine, '9');
 
-    let zero = get_ambiguous_code(&remaining, 6, &eight_remainder, false);
+    let zero = get_ambiguous_code(&remaining, 6, &eight_remainder, false)?;
     codes.insert(zero, '0');
 
-    codes
+    Some(codes)
 }
 
-fn parse_to_digits(codes: &HashMap<String, char>, digit_strings: &Vec<String>) -> String {
+fn parse_to_digits(
+    line: &InputLine,
+    codes: &HashMap<String, char>,
+    digit_strings: &str,
+) -> Result<String, ParseError> {
     digit_strings
-        .iter()
-        .map(|s| *codes.get(s).unwrap())
+        .split_whitespace()
+        .map(|s| {
+            let sorted = s.chars().sorted().collect::<String>();
+            codes
+                .get(&sorted)
+                .copied()
+                .ok_or_else(|| line.error(s, format!("Unknown digit '{}'", s)))
+        })
         .collect()
 }
 
-fn get_digits_for_line(s: &str) -> Result<String> {
-    let mut parts: Vec<Vec<String>> = s
-        .split("|")
-        .map(|s| {
-            s.split_whitespace()
-                .map(|s| s.trim())
-                .filter(|s| !s.is_empty())
-                .map(|s| s.chars().sorted().collect::<String>())
-                .collect()
-        })
-        .collect();
+fn get_digits_for_line(line: &InputLine) -> Result<String, ParseError> {
+    let (patterns, output) = line.split_once(&line.text, "|")?;
 
-    let left = get_codes(parts.remove(0));
-    let digits = parse_to_digits(&left, &parts.remove(0));
+    let unexpected = line
+        .text
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !matches!(c, 'a'..='g' | '|' | ' '));
+    if let Some((i, c)) = unexpected {
+        return Err(line.error_at(i + 1, format!("Unexpected character '{}'", c)));
+    }
 
-    Ok(digits)
+    let patterns = patterns
+        .split_whitespace()
+        .map(|s| s.chars().sorted().collect::<String>())
+        .collect();
+    let codes = get_codes(patterns).ok_or_else(|| {
+        line.error(
+            &line.text,
+            "Unable to work out the wiring from the patterns",
+        )
+    })?;
+
+    parse_to_digits(line, &codes, output)
 }
 
 fn part1(lines: Vec<String>) -> Result<usize> {
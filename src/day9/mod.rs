@@ -1,141 +1,94 @@
-use crate::arguments::Arguments;
-use crate::io::reader;
+use crate::common::grid::Grid;
+use crate::common::point::Position;
+use crate::common::search::reachable;
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, Example, Solver};
 use anyhow::Result;
-use std::collections::HashSet;
-use std::io::BufRead;
-
-pub fn day9(args: &Arguments) -> Result<()> {
-    let reader = reader(args.src_file.as_ref())?;
-
-    let mut lines: Vec<Vec<u8>> = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        let line = line
-            .chars()
-            .into_iter()
-            .map(|c| c.to_string().parse().unwrap())
-            .collect();
-        lines.push(line);
-    }
-    let lines = lines;
-
-    let result = if args.part == 1 {
-        part1(lines)
-    } else {
-        part2(lines)
-    }?;
+use std::cmp::Reverse;
 
-    info!("{:?}", result);
+const EXAMPLE: &str = "\
+2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+";
 
-    Ok(())
-}
+pub struct Day9;
 
-#[derive(Debug, Default, Eq, PartialEq, Hash)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-
-#[derive(Debug, Default)]
-struct LowPoint {
-    point: Point,
-    value: u8,
-}
+impl Solver for Day9 {
+    type Input = Grid<u8>;
 
-fn adjacent_points(grid: &Vec<Vec<u8>>, x: usize, y: usize) -> Vec<Point> {
-    let mut points = Vec::new();
-    if x > 0 {
-        points.push(Point { x: x - 1, y })
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input> {
+        Grid::from_digit_lines(input)
     }
-    if x < grid[y].len() - 1 {
-        points.push(Point { x: x + 1, y })
+
+    fn part1(&self, grid: Self::Input) -> Result<Answer> {
+        part1(grid).map(Answer::from)
     }
-    if y > 0 {
-        points.push(Point { x, y: y - 1 })
+
+    fn part2(&self, grid: Self::Input) -> Result<Answer> {
+        part2(grid).map(Answer::from)
     }
-    if y < grid.len() - 1 {
-        points.push(Point { x, y: y + 1 })
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(EXAMPLE, 15usize, 1134usize)]
     }
-    points
 }
 
-fn is_low_point(grid: &Vec<Vec<u8>>, x: usize, y: usize) -> bool {
-    let value = grid[y][x];
-
-    !adjacent_points(grid, x, y)
-        .iter()
-        .any(|point| grid[point.y][point.x] <= value)
+#[derive(Debug, Default)]
+struct LowPoint {
+    point: Position,
+    value: u8,
 }
 
-fn find_low_points(grid: &Vec<Vec<u8>>) -> Vec<LowPoint> {
-    let mut low_points = Vec::new();
-    for y in 0..grid.len() {
-        for x in 0..grid[y].len() {
-            if is_low_point(grid, x, y) {
-                low_points.push(LowPoint {
-                    point: Point { x, y },
-                    value: grid[y][x],
-                })
-            }
-        }
-    }
+fn is_low_point(grid: &Grid<u8>, p: Position) -> bool {
+    let value = grid[p];
 
-    low_points
+    !grid.neighbors4(p).any(|point| grid[point] <= value)
 }
 
-#[derive(Debug, Default)]
-struct Basin {
-    low: LowPoint,
-    points: HashSet<Point>,
+fn find_low_points(grid: &Grid<u8>) -> Vec<LowPoint> {
+    grid.iter()
+        .filter(|(point, _)| is_low_point(grid, *point))
+        .map(|(point, value)| LowPoint {
+            point,
+            value: *value,
+        })
+        .collect()
 }
 
-fn explore_basin(grid: &Vec<Vec<u8>>, basin: &mut Basin, x: usize, y: usize) {
-    if basin.points.contains(&Point { x, y }) {
-        return;
-    }
-    let value = grid[y][x];
-    if value >= 9 {
-        return;
-    }
-    basin.points.insert(Point { x, y });
-    let adjacent: Vec<Point> = adjacent_points(grid, x, y)
-        .into_iter()
-        .filter(|point| !basin.points.contains(point))
-        .collect();
-
-    for point in adjacent {
-        explore_basin(grid, basin, point.x, point.y);
-    }
+/// Every point that flows down to `low`, which is everything reachable from it without crossing a 9.
+fn explore_basin(grid: &Grid<u8>, low: &LowPoint) -> Vec<Position> {
+    reachable(low.point, |p| {
+        grid.neighbors4(*p)
+            .filter(|point| grid[*point] < 9)
+            .map(|point| (point, 1))
+    })
 }
 
-fn part1(grid: Vec<Vec<u8>>) -> Result<usize> {
+fn part1(grid: Grid<u8>) -> Result<usize> {
     let result = find_low_points(&grid)
         .iter()
-        .fold(0, |accum, x| accum + (x.value as usize) + 1);
+        .map(|x| (x.value as usize) + 1)
+        .sum();
     Ok(result)
 }
 
-fn part2(grid: Vec<Vec<u8>>) -> Result<usize> {
-    let mut basins: Vec<Basin> = find_low_points(&grid)
-        .into_iter()
-        .map(|low| Basin {
-            low,
-            ..Default::default()
-        })
+fn part2(grid: Grid<u8>) -> Result<usize> {
+    let mut basins: Vec<Vec<Position>> = find_low_points(&grid)
+        .iter()
+        .map(|low| explore_basin(&grid, low))
         .collect();
 
-    basins
-        .iter_mut()
-        .for_each(|basin| explore_basin(&grid, basin, basin.low.point.x, basin.low.point.y));
-
     // we want to sort in reverse to have the largest numbers at the front
-    basins.sort_by(|a, b| b.points.len().cmp(&a.points.len()));
+    basins.sort_by_key(|basin| Reverse(basin.len()));
 
-    let first = basins.get(0).unwrap();
+    let first = basins.first().unwrap();
     let second = basins.get(1).unwrap();
     let third = basins.get(2).unwrap();
 
-    let result = first.points.len() * second.points.len() * third.points.len();
+    let result = first.len() * second.len() * third.len();
 
     Ok(result)
 }
@@ -1,11 +1,17 @@
-use anyhow::Result;
+use crate::common::parse::{InputLine, ParseError};
+use anyhow::{Context, Result};
 use std::fs::File;
 use std::io;
+use std::io::{BufRead, Read};
+use std::sync::Arc;
+
+/// Passing this as the input file reads the input from stdin.
+pub const STDIN: &str = "-";
 
 pub fn reader(filename: Option<&String>) -> Result<Box<dyn io::BufRead>> {
     let reader: Box<dyn io::Read> = match filename {
         Some(name) => {
-            let file = File::open(name)?;
+            let file = File::open(name).with_context(|| format!("Unable to open {}", name))?;
             Box::new(io::BufReader::new(file))
         }
         None => Box::new(io::stdin()),
@@ -13,3 +19,105 @@ pub fn reader(filename: Option<&String>) -> Result<Box<dyn io::BufRead>> {
 
     Ok(Box::new(io::BufReader::new(reader)))
 }
+
+/// Reads the whole input into memory, e.g. so it can be parsed more than once.
+pub fn read_all(filename: Option<&String>) -> Result<Vec<u8>> {
+    let mut contents = Vec::new();
+    reader(filename)?.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+/// The path of a day's input file, e.g. 'input/day15.input', or 'input/day15-test.input' for the
+/// 'test' variant.
+pub fn input_path(input_dir: &str, day: u32, variant: Option<&str>) -> String {
+    match variant {
+        Some(variant) => format!("{}/day{}-{}.input", input_dir, day, variant),
+        None => format!("{}/day{}.input", input_dir, day),
+    }
+}
+
+/// Puzzle input along with a name for it, so parse errors can say where they happened.
+pub struct PuzzleInput {
+    name: String,
+    reader: Box<dyn io::BufRead>,
+}
+
+impl PuzzleInput {
+    pub fn new<N>(name: N, reader: Box<dyn io::BufRead>) -> PuzzleInput
+    where
+        N: Into<String>,
+    {
+        PuzzleInput {
+            name: name.into(),
+            reader,
+        }
+    }
+
+    /// Input already in memory, e.g. read from stdin up front or written in a test.
+    pub fn from_contents<N, C>(name: N, contents: C) -> PuzzleInput
+    where
+        N: Into<String>,
+        C: Into<Vec<u8>>,
+    {
+        PuzzleInput::new(name, Box::new(io::Cursor::new(contents.into())))
+    }
+
+    /// Opens the file, or stdin if there isn't one.
+    pub fn open(filename: Option<&String>) -> Result<PuzzleInput> {
+        let name = filename.map_or("stdin", |name| name.as_str()).to_owned();
+        Ok(PuzzleInput::new(name, reader(filename)?))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The raw input, for solvers that don't read it a line at a time.
+    pub fn into_reader(self) -> Box<dyn io::BufRead> {
+        self.reader
+    }
+
+    pub fn lines(self) -> InputLines {
+        InputLines {
+            file: Arc::from(self.name),
+            lines: self.reader.lines(),
+            number: 0,
+        }
+    }
+}
+
+pub struct InputLines {
+    file: Arc<str>,
+    lines: io::Lines<Box<dyn io::BufRead>>,
+    number: usize,
+}
+
+impl InputLines {
+    /// The next line, or an error if the input ended early.
+    pub fn expect_line(&mut self) -> Result<InputLine> {
+        match self.next() {
+            Some(line) => line,
+            None => Err(ParseError {
+                file: self.file.to_string(),
+                line: self.number + 1,
+                column: 1,
+                text: String::new(),
+                message: "Unexpected end of input".to_owned(),
+            }
+            .into()),
+        }
+    }
+}
+
+impl Iterator for InputLines {
+    type Item = Result<InputLine>;
+
+    fn next(&mut self) -> Option<Result<InputLine>> {
+        let text = self.lines.next()?;
+        self.number += 1;
+        Some(
+            text.with_context(|| format!("Unable to read {}", self.file))
+                .map(|text| InputLine::new(self.file.clone(), self.number, text)),
+        )
+    }
+}
@@ -1,8 +1,10 @@
 #[macro_use]
 extern crate log;
-use bitvec::prelude::*;
 
+pub mod answers;
 pub mod arguments;
+pub mod bench;
+pub mod cache;
 pub mod common;
 pub mod day1;
 pub mod day10;
@@ -21,3 +23,8 @@ pub mod day7;
 pub mod day8;
 pub mod day9;
 pub mod io;
+pub mod logging;
+pub mod output;
+pub mod runner;
+pub mod settings;
+pub mod solver;
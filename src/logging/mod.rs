@@ -0,0 +1,67 @@
+use crate::settings::Settings;
+use anyhow::Result;
+use env_logger::Builder;
+use log::LevelFilter;
+use std::env;
+use std::str::FromStr;
+
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::Off,
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+/// Moves the level up (more verbose) or down (quieter) by `verbosity` steps.
+fn adjust_level(level: LevelFilter, verbosity: i32) -> LevelFilter {
+    let index = LEVELS.iter().position(|l| *l == level).unwrap_or(0) as i32;
+    let index = (index + verbosity).clamp(0, LEVELS.len() as i32 - 1);
+    LEVELS[index as usize]
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter> {
+    LevelFilter::from_str(level.trim())
+        .map_err(|_| anyhow::Error::msg(format!("Invalid log level: {}", level)))
+}
+
+fn parse_modules(modules: &str) -> Result<Vec<(String, LevelFilter)>> {
+    modules
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let (module, level) = s.split_once('=').ok_or_else(|| {
+                anyhow::Error::msg(format!("Expected module=level but got {}", s))
+            })?;
+            let module = module.trim();
+            let module = if module.contains("::") {
+                module.to_owned()
+            } else {
+                format!("{}::{}", env!("CARGO_CRATE_NAME"), module)
+            };
+            Ok((module, parse_level(level)?))
+        })
+        .collect()
+}
+
+/// Initializes the env_logger crate. Everything is logged to stderr so that answers on stdout
+/// aren't mixed in with diagnostics.
+///
+/// The global level comes from the settings, adjusted by `-v`/`-q`. `RUST_LOG` is still honored
+/// and takes precedence over both.
+pub fn init(settings: &Settings, verbosity: i32) -> Result<()> {
+    let mut builder = Builder::new();
+
+    builder.filter_level(adjust_level(parse_level(&settings.log_level)?, verbosity));
+    for (module, level) in parse_modules(&settings.log_modules)? {
+        builder.filter_module(&module, level);
+    }
+    if let Ok(filters) = env::var("RUST_LOG") {
+        builder.parse_filters(&filters);
+    }
+
+    builder.try_init()?;
+    Ok(())
+}
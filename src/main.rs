@@ -1,62 +1,256 @@
-use advent_of_code_2021::arguments::Arguments;
-use advent_of_code_2021::day1::day1;
-use advent_of_code_2021::day10::day10;
-use advent_of_code_2021::day11::day11;
-use advent_of_code_2021::day12::day12;
-use advent_of_code_2021::day13::day13;
-use advent_of_code_2021::day14::day14;
-use advent_of_code_2021::day15::day15;
-use advent_of_code_2021::day16::day16;
-use advent_of_code_2021::day2::day2;
-use advent_of_code_2021::day3::day3;
-use advent_of_code_2021::day4::day4;
-use advent_of_code_2021::day5::day5;
-use advent_of_code_2021::day6::day6;
-use advent_of_code_2021::day7::day7;
-use advent_of_code_2021::day8::day8;
-use advent_of_code_2021::day9::day9;
+use advent_of_code_2021::answers::{Answers, Check};
+use advent_of_code_2021::arguments::{
+    Arguments, BitsArguments, CacheAction, CacheArguments, Command,
+};
+use advent_of_code_2021::bench::{bench, bench_table, write_benchmarks};
+use advent_of_code_2021::cache::{InputCache, Status};
+use advent_of_code_2021::day12::Day12;
+use advent_of_code_2021::day13::Day13;
+use advent_of_code_2021::day15::{Day15, DEFAULT_TILE_FACTOR};
+use advent_of_code_2021::day16::{self, Day16};
+use advent_of_code_2021::io::{read_all, reader, PuzzleInput, STDIN};
+use advent_of_code_2021::logging;
+use advent_of_code_2021::output::{format_runs, OutputFormat};
+use advent_of_code_2021::runner::{run, run_input};
+use advent_of_code_2021::settings::Settings;
+use advent_of_code_2021::solver::{Part, Registry};
 use anyhow::Result;
 use dotenv::dotenv;
-use env_logger::Builder;
-use log::LevelFilter;
+use std::io::{BufRead, IsTerminal, Write};
 #[macro_use]
 extern crate log;
 
 fn main() -> Result<()> {
     dotenv().ok();
 
-    // Initialize the env_logger crate
-    Builder::from_default_env()
-        .filter_level(LevelFilter::Debug)
-        .init();
-
-    let args = Arguments::parse_args();
-
-    info!("args: {:?}", args);
-
-    match args.day {
-        1 => day1(&args)?,
-        2 => day2(&args)?,
-        3 => day3(&args)?,
-        4 => day4(&args)?,
-        5 => day5(&args)?,
-        6 => day6(&args)?,
-        7 => day7(&args)?,
-        8 => day8(&args)?,
-        9 => day9(&args)?,
-        10 => day10(&args)?,
-        11 => day11(&args)?,
-        12 => day12(&args)?,
-        13 => day13(&args)?,
-        14 => day14(&args)?,
-        15 => day15(&args)?,
-        16 => day16(&args)?,
-        _ => {
-            return Err(anyhow::Error::msg(format!(
-                "Unrecognized day number: {}",
-                args.day
-            )))
+    let command = Command::parse_args()?;
+    let settings = Settings::load()?;
+    logging::init(&settings, command.verbosity())?;
+
+    debug!("command: {:?}", command);
+
+    match command {
+        Command::Solve(args) => solve(*args, &settings),
+        Command::Cache(args) => cache(args, &settings),
+        Command::Bits(args) => bits(args),
+    }
+}
+
+/// Explains each line of hex as a BITS transmission. Bad lines are reported and skipped, so it can
+/// be used interactively.
+fn bits(args: BitsArguments) -> Result<()> {
+    let src_file = args.src_file.filter(|file| file != STDIN);
+    let interactive = src_file.is_none() && std::io::stdin().is_terminal();
+    let prompt = || {
+        if interactive {
+            eprint!("> ");
+            std::io::stderr().flush().ok();
+        }
+    };
+
+    prompt();
+    for line in reader(src_file.as_ref())?.lines() {
+        let line = line?;
+        let hex = line.trim();
+        if !hex.is_empty() {
+            match day16::explain(hex) {
+                Ok(explanation) => println!("{}", explanation),
+                Err(e) => error!("{}: {:#}", hex, e),
+            }
+        }
+        prompt();
+    }
+
+    Ok(())
+}
+
+fn cache(args: CacheArguments, settings: &Settings) -> Result<()> {
+    let profile = args.profile.as_deref().unwrap_or(&settings.profile);
+    let mut cache = InputCache::open(&settings.cache_dir, args.year, profile)?;
+
+    match args.action {
+        CacheAction::Import => {
+            let day = args.day.unwrap();
+            let path = cache.import(day, args.src_file.as_ref().unwrap())?;
+            info!("Imported day {} to {}", day, path.display());
+        }
+        CacheAction::List => {
+            for (day, entry) in cache.entries() {
+                if args.day.is_some() && args.day != Some(day) {
+                    continue;
+                }
+                let status = match cache.status(day)? {
+                    Status::Ok => "ok",
+                    Status::Modified => "modified",
+                    Status::Missing => "missing",
+                };
+                println!(
+                    "day{}\t{}\t{}\t{}",
+                    day, status, entry.checksum, entry.source
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Every day, with the day specific options from the command line applied.
+fn registry(args: &Arguments) -> Registry {
+    let mut registry = Registry::default();
+    if args.list_paths || args.policy.is_some() || args.export_dir.is_some() {
+        let day12 = Day12::new(args.list_paths, args.policy.clone(), args.export());
+        registry.register(12, day12);
+    }
+    if args.frames.is_some() {
+        registry.register(13, Day13::new(args.frames.clone()));
+    }
+    if args.render.is_some() || args.tile_factor.is_some() {
+        let tile_factor = args.tile_factor.unwrap_or(DEFAULT_TILE_FACTOR);
+        registry.register(15, Day15::new(tile_factor, args.render()));
+    }
+    if let Some(numeric) = args.numeric {
+        registry.register(16, Day16::new(numeric));
+    }
+    registry
+}
+
+fn solve(args: Arguments, settings: &Settings) -> Result<()> {
+    let registry = registry(&args);
+
+    let days = match args.days {
+        Some(range) if range.is_single_day() => {
+            if registry.get(range.first).is_none() {
+                return Err(anyhow::Error::msg(format!(
+                    "Unrecognized day number: {}",
+                    range.first
+                )));
+            }
+            vec![range.first]
+        }
+        Some(range) => {
+            let days = registry
+                .days()
+                .filter(|day| range.contains(*day))
+                .collect::<Vec<u32>>();
+            if days.is_empty() {
+                return Err(anyhow::Error::msg(format!(
+                    "No days between {} and {} are implemented",
+                    range.first, range.last
+                )));
+            }
+            days
+        }
+        None => registry.days().collect(),
+    };
+
+    let parts = match args.part {
+        Some(part) => vec![Part::from_number(part)?],
+        None => vec![Part::One, Part::Two],
+    };
+
+    // stdin can only be read once, so hold on to it for every part that needs it
+    let stdin = if args.reads_stdin() {
+        Some(read_all(None)?)
+    } else {
+        None
+    };
+
+    if let Some(iterations) = args.bench {
+        let mut benchmarks = Vec::new();
+        for day in days {
+            let solver = registry.get(day).unwrap();
+            let input_file = args.input_file(day, settings);
+            let name = input_file.as_deref().unwrap_or("stdin");
+            let contents = match &stdin {
+                Some(contents) => contents.clone(),
+                None => read_all(input_file.as_ref())?,
+            };
+            for part in &parts {
+                benchmarks.push(bench(solver, day, *part, name, &contents, iterations)?);
+            }
+        }
+
+        println!("{}", bench_table(&benchmarks));
+        if let Some(path) = &args.bench_output {
+            write_benchmarks(path, &benchmarks)?;
+        }
+        return Ok(());
+    }
+
+    let mut runs = Vec::new();
+    for day in days {
+        let solver = registry.get(day).unwrap();
+        for part in &parts {
+            runs.push(match &stdin {
+                Some(contents) => {
+                    let input = PuzzleInput::from_contents("stdin", contents.clone());
+                    run_input(solver, day, *part, input)
+                }
+                None => run(solver, day, *part, args.input_file(day, settings).as_ref()),
+            });
+        }
+    }
+
+    let format = args.output.unwrap_or(if runs.len() == 1 {
+        OutputFormat::Plain
+    } else {
+        OutputFormat::Table
+    });
+    let output = format_runs(&runs, format)?;
+    if !output.is_empty() {
+        println!("{}", output);
+    }
+
+    let mut num_mismatches = 0;
+    if args.check || args.record {
+        let mut answers = Answers::load(&args.answers_file)?;
+
+        for run in &runs {
+            let answer = match &run.answer {
+                Ok(answer) => answer,
+                Err(_) => continue,
+            };
+
+            if args.check {
+                match answers.check(run.day, run.part, answer) {
+                    Check::Match => {}
+                    Check::Mismatch { expected, actual } => {
+                        error!(
+                            "Day {} part {}: expected {} but got {}",
+                            run.day, run.part, expected, actual
+                        );
+                        num_mismatches += 1;
+                    }
+                    Check::NotRecorded => {
+                        warn!("Day {} part {}: no recorded answer", run.day, run.part);
+                    }
+                }
+            }
+            if args.record {
+                answers.set(run.day, run.part, answer);
+            }
         }
+
+        if args.record {
+            answers.save()?;
+        }
+    }
+
+    for run in &runs {
+        if let Err(e) = &run.answer {
+            error!("Day {} part {}: {:#}", run.day, run.part, e);
+        }
+    }
+
+    let num_failed = runs.iter().filter(|run| !run.is_ok()).count();
+    if num_failed > 0 || num_mismatches > 0 {
+        return Err(anyhow::Error::msg(format!(
+            "{} of {} runs failed, {} answers did not match",
+            num_failed,
+            runs.len(),
+            num_mismatches
+        )));
     }
 
     Ok(())
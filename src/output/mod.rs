@@ -0,0 +1,176 @@
+use crate::runner::Run;
+use crate::solver::Answer;
+use anyhow::Result;
+use serde_derive::Serialize;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How answers are written to stdout. Logging always goes to stderr, so every format other than
+/// `Table` is safe to consume from scripts.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// A human readable table with timings
+    Table,
+    /// Only the answers, one line per run in the order they were run, with an empty line for a
+    /// run that failed
+    Plain,
+    /// A JSON array with one object per run
+    Json,
+    /// Tab separated values with a header row
+    Tsv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<OutputFormat> {
+        match s {
+            "table" => Ok(OutputFormat::Table),
+            "plain" => Ok(OutputFormat::Plain),
+            "json" => Ok(OutputFormat::Json),
+            "tsv" => Ok(OutputFormat::Tsv),
+            _ => Err(anyhow::Error::msg(format!(
+                "Unrecognized output format {}",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRun<'a> {
+    day: u32,
+    part: u32,
+    answer: Option<&'a Answer>,
+    error: Option<String>,
+    parse_ns: Option<u64>,
+    solve_ns: Option<u64>,
+}
+
+pub fn format_runs(runs: &[Run], format: OutputFormat) -> Result<String> {
+    let output = match format {
+        OutputFormat::Table => summary_table(runs),
+        // failed runs still get a line, so each answer stays on the line for its run
+        OutputFormat::Plain => runs
+            .iter()
+            .map(|run| match &run.answer {
+                Ok(answer) => answer.to_string(),
+                Err(_) => String::new(),
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+        OutputFormat::Json => {
+            let runs = runs
+                .iter()
+                .map(|run| JsonRun {
+                    day: run.day,
+                    part: run.part.number(),
+                    answer: run.answer.as_ref().ok(),
+                    error: run.answer.as_ref().err().map(|e| format!("{:#}", e)),
+                    parse_ns: run.parse_time.map(|d| d.as_nanos() as u64),
+                    solve_ns: run.solve_time.map(|d| d.as_nanos() as u64),
+                })
+                .collect::<Vec<JsonRun>>();
+            serde_json::to_string_pretty(&runs)?
+        }
+        OutputFormat::Tsv => {
+            let mut lines = vec!["day\tpart\tanswer\terror\tparse_ns\tsolve_ns".to_owned()];
+            for run in runs {
+                let (answer, error) = match &run.answer {
+                    Ok(answer) => (escape_tsv(&answer.to_string()), String::new()),
+                    Err(e) => (String::new(), escape_tsv(&format!("{:#}", e))),
+                };
+                let nanos =
+                    |d: Option<Duration>| d.map(|d| d.as_nanos().to_string()).unwrap_or_default();
+                lines.push(format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    run.day,
+                    run.part,
+                    answer,
+                    error,
+                    nanos(run.parse_time),
+                    nanos(run.solve_time)
+                ));
+            }
+            lines.join("\n")
+        }
+    };
+
+    Ok(output)
+}
+
+fn escape_tsv(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+/// Renders a table with one row per run.
+fn summary_table(runs: &[Run]) -> String {
+    let header = vec![
+        "Day".to_owned(),
+        "Part".to_owned(),
+        "Answer".to_owned(),
+        "Parse".to_owned(),
+        "Solve".to_owned(),
+    ];
+
+    let rows = runs
+        .iter()
+        .map(|run| {
+            vec![
+                run.day.to_string(),
+                run.part.to_string(),
+                match &run.answer {
+                    Ok(answer) => answer.to_string().replace('\n', "\\n"),
+                    // the full error, with any context lines, is logged separately
+                    Err(e) => {
+                        let e = format!("{:#}", e);
+                        format!("error: {}", e.lines().next().unwrap_or_default())
+                    }
+                },
+                format_duration(run.parse_time),
+                format_duration(run.solve_time),
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    let mut widths = header.iter().map(|s| s.len()).collect::<Vec<usize>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join(" | ")
+            .trim_end()
+            .to_owned()
+    };
+
+    let mut table = Vec::new();
+    table.push(format_row(&header));
+    table.push(
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<String>>()
+            .join("-+-"),
+    );
+    for row in &rows {
+        table.push(format_row(row));
+    }
+
+    table.join("\n")
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => format!("{:.3?}", duration),
+        None => "-".to_owned(),
+    }
+}
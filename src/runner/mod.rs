@@ -0,0 +1,64 @@
+use crate::io::PuzzleInput;
+use crate::solver::{Answer, DynSolver, Part};
+use anyhow::Result;
+use std::time::{Duration, Instant};
+
+/// The outcome of solving one part of one day.
+#[derive(Debug)]
+pub struct Run {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Result<Answer>,
+    pub parse_time: Option<Duration>,
+    pub solve_time: Option<Duration>,
+}
+
+impl Run {
+    pub fn is_ok(&self) -> bool {
+        self.answer.is_ok()
+    }
+}
+
+/// Parses the input file (or stdin if there isn't one) and solves a single part, timing each step
+/// separately. Errors are captured in the returned [`Run`] rather than propagated, so a caller
+/// running many days can keep going.
+pub fn run(solver: &dyn DynSolver, day: u32, part: Part, input_file: Option<&String>) -> Run {
+    match PuzzleInput::open(input_file) {
+        Ok(input) => run_input(solver, day, part, input),
+        Err(e) => Run {
+            day,
+            part,
+            answer: Err(e),
+            parse_time: None,
+            solve_time: None,
+        },
+    }
+}
+
+/// Same as [`run`], but reads from already opened input.
+pub fn run_input(solver: &dyn DynSolver, day: u32, part: Part, input: PuzzleInput) -> Run {
+    let mut run = Run {
+        day,
+        part,
+        answer: Err(anyhow::Error::msg("Not run")),
+        parse_time: None,
+        solve_time: None,
+    };
+
+    let start = Instant::now();
+    let input = solver.parse(input);
+    run.parse_time = Some(start.elapsed());
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => {
+            run.answer = Err(e);
+            return run;
+        }
+    };
+
+    let start = Instant::now();
+    run.answer = solver.solve_parsed(part, input);
+    run.solve_time = Some(start.elapsed());
+
+    run
+}
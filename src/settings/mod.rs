@@ -0,0 +1,48 @@
+use anyhow::Result;
+use config::{Config, Environment, File};
+use serde_derive::Deserialize;
+
+/// Name of the optional settings file, e.g. `aoc.toml`, in the working directory.
+pub const SETTINGS_FILE: &str = "aoc";
+
+/// Settings that don't change from run to run. They're read from the settings file and then from
+/// `AOC_`-prefixed environment variables (which may come from `.env`), e.g.
+///
+/// ```toml
+/// log_level = "info"
+/// log_modules = "day15=debug,config=warn"
+/// input_dir = "/home/me/aoc-inputs"
+/// cache_dir = "/home/me/.cache/aoc"
+/// profile = "work"
+/// ```
+///
+/// or `AOC_LOG_LEVEL=debug`.
+#[derive(Debug, Deserialize)]
+pub struct Settings {
+    /// The global log level
+    pub log_level: String,
+    /// Comma separated `module=level` pairs. Modules without a `::` are taken to be relative to
+    /// this crate, so `day15` refers to `advent_of_code_2021::day15`.
+    pub log_modules: String,
+    /// Where the 'day<number>.input' files live
+    pub input_dir: String,
+    /// Root of the managed input cache, see [`crate::cache::InputCache`]
+    pub cache_dir: String,
+    /// Which of the cached input sets to use
+    pub profile: String,
+}
+
+impl Settings {
+    pub fn load() -> Result<Settings> {
+        let mut config = Config::default();
+        config.set_default("log_level", "info")?;
+        config.set_default("log_modules", "")?;
+        config.set_default("input_dir", "input")?;
+        config.set_default("cache_dir", "cache")?;
+        config.set_default("profile", "default")?;
+        config.merge(File::with_name(SETTINGS_FILE).required(false))?;
+        config.merge(Environment::with_prefix("AOC"))?;
+
+        Ok(config.try_into()?)
+    }
+}
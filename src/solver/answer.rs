@@ -0,0 +1,56 @@
+use num_bigint::BigUint;
+use serde_derive::Serialize;
+use std::fmt;
+
+/// The answer to one part of a day's puzzle.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Answer {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Answer {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Answer {
+        Answer::Signed(value)
+    }
+}
+
+/// Small enough values are `Unsigned`, so they compare equal to answers computed with a `u64`.
+impl From<BigUint> for Answer {
+    fn from(value: BigUint) -> Answer {
+        match u64::try_from(&value) {
+            Ok(value) => Answer::Unsigned(value),
+            Err(_) => Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        Answer::Text(value)
+    }
+}
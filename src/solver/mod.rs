@@ -0,0 +1,153 @@
+use crate::io::PuzzleInput;
+use anyhow::Result;
+use std::any::Any;
+use std::fmt;
+
+mod answer;
+mod registry;
+
+pub use answer::Answer;
+pub use registry::Registry;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn from_number(num: u32) -> Result<Part> {
+        match num {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(anyhow::Error::msg(format!(
+                "Unrecognized part number: {}",
+                num
+            ))),
+        }
+    }
+
+    pub fn number(&self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// The puzzle's example input along with the expected answer for each part. Some puzzles only
+/// give an example for one of the parts, so either answer may be left out.
+#[derive(Debug, Clone)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Example {
+    pub fn new<A1, A2>(input: &'static str, part1: A1, part2: A2) -> Example
+    where
+        A1: Into<Answer>,
+        A2: Into<Answer>,
+    {
+        Example {
+            input,
+            part1: Some(part1.into()),
+            part2: Some(part2.into()),
+        }
+    }
+
+    pub fn only_part1<A>(input: &'static str, part1: A) -> Example
+    where
+        A: Into<Answer>,
+    {
+        Example {
+            input,
+            part1: Some(part1.into()),
+            part2: None,
+        }
+    }
+
+    pub fn only_part2<A>(input: &'static str, part2: A) -> Example
+    where
+        A: Into<Answer>,
+    {
+        Example {
+            input,
+            part1: None,
+            part2: Some(part2.into()),
+        }
+    }
+
+    pub fn expected(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+/// The solution for a single day. `parse` turns the raw puzzle input into the day's own input
+/// type, which is then handed to whichever part is being solved.
+pub trait Solver {
+    type Input;
+
+    fn parse(&self, input: PuzzleInput) -> Result<Self::Input>;
+
+    fn part1(&self, input: Self::Input) -> Result<Answer>;
+
+    fn part2(&self, input: Self::Input) -> Result<Answer>;
+
+    /// Examples from the puzzle description, used by the test harness.
+    fn examples(&self) -> Vec<Example> {
+        Vec::new()
+    }
+}
+
+/// Object safe version of [`Solver`] so solvers for different days can live in the same
+/// [`Registry`]. The parsed input is type erased, so it should only be handed back to the
+/// solver that produced it.
+pub trait DynSolver {
+    fn parse(&self, input: PuzzleInput) -> Result<Box<dyn Any>>;
+
+    fn solve_parsed(&self, part: Part, input: Box<dyn Any>) -> Result<Answer>;
+
+    fn solve(&self, part: Part, input: PuzzleInput) -> Result<Answer> {
+        let input = self.parse(input)?;
+        self.solve_parsed(part, input)
+    }
+
+    fn examples(&self) -> Vec<Example>;
+}
+
+impl<S> DynSolver for S
+where
+    S: Solver,
+    S::Input: 'static,
+{
+    fn parse(&self, input: PuzzleInput) -> Result<Box<dyn Any>> {
+        let input = Solver::parse(self, input)?;
+        Ok(Box::new(input))
+    }
+
+    fn solve_parsed(&self, part: Part, input: Box<dyn Any>) -> Result<Answer> {
+        let input = input
+            .downcast::<S::Input>()
+            .map_err(|_| anyhow::Error::msg("Parsed input does not belong to this solver"))?;
+
+        match part {
+            Part::One => self.part1(*input),
+            Part::Two => self.part2(*input),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        Solver::examples(self)
+    }
+}
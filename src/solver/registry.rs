@@ -0,0 +1,60 @@
+use crate::solver::{DynSolver, Solver};
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day2, day3, day4, day5, day6, day7,
+    day8, day9,
+};
+use std::collections::BTreeMap;
+
+/// Maps day numbers to their solvers.
+pub struct Registry {
+    solvers: BTreeMap<u32, Box<dyn DynSolver>>,
+}
+
+impl Registry {
+    /// An empty registry. Use [`Registry::default`] to get one with every day registered.
+    pub fn new() -> Registry {
+        Registry {
+            solvers: BTreeMap::new(),
+        }
+    }
+
+    pub fn register<S>(&mut self, day: u32, solver: S)
+    where
+        S: Solver + 'static,
+        S::Input: 'static,
+    {
+        self.solvers.insert(day, Box::new(solver));
+    }
+
+    pub fn get(&self, day: u32) -> Option<&dyn DynSolver> {
+        self.solvers.get(&day).map(|solver| solver.as_ref())
+    }
+
+    /// The registered day numbers, in ascending order.
+    pub fn days(&self) -> impl Iterator<Item = u32> + '_ {
+        self.solvers.keys().copied()
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Registry {
+        let mut registry = Registry::new();
+        registry.register(1, day1::Day1);
+        registry.register(2, day2::Day2);
+        registry.register(3, day3::Day3);
+        registry.register(4, day4::Day4);
+        registry.register(5, day5::Day5);
+        registry.register(6, day6::Day6);
+        registry.register(7, day7::Day7);
+        registry.register(8, day8::Day8);
+        registry.register(9, day9::Day9);
+        registry.register(10, day10::Day10);
+        registry.register(11, day11::Day11);
+        registry.register(12, day12::Day12::default());
+        registry.register(13, day13::Day13::default());
+        registry.register(14, day14::Day14);
+        registry.register(15, day15::Day15::default());
+        registry.register(16, day16::Day16::default());
+        registry
+    }
+}
@@ -0,0 +1,52 @@
+//! Checks the timing statistics reported by `--bench`.
+
+use advent_of_code_2021::bench::Stats;
+use std::time::Duration;
+
+fn stats(nanos: &[u64]) -> Stats {
+    Stats::from_samples(
+        &nanos
+            .iter()
+            .map(|n| Duration::from_nanos(*n))
+            .collect::<Vec<Duration>>(),
+    )
+}
+
+fn expected(min_ns: u64, median_ns: u64, mean_ns: u64, max_ns: u64) -> Stats {
+    Stats {
+        min_ns,
+        median_ns,
+        mean_ns,
+        max_ns,
+    }
+}
+
+#[test]
+fn no_samples() {
+    assert_eq!(stats(&[]), expected(0, 0, 0, 0));
+}
+
+#[test]
+fn one_sample() {
+    assert_eq!(stats(&[42]), expected(42, 42, 42, 42));
+}
+
+#[test]
+fn odd_number_of_samples() {
+    // unsorted, to check the median comes from the sorted samples
+    assert_eq!(stats(&[30, 10, 20]), expected(10, 20, 20, 30));
+    assert_eq!(stats(&[1, 100, 2, 3, 4]), expected(1, 3, 22, 100));
+}
+
+#[test]
+fn even_number_of_samples() {
+    // the median is the mean of the middle two, rounded down
+    assert_eq!(stats(&[40, 10, 30, 20]), expected(10, 25, 25, 40));
+    assert_eq!(stats(&[1, 2]), expected(1, 1, 1, 2));
+}
+
+#[test]
+fn mean_rounds_down() {
+    assert_eq!(stats(&[1, 1, 2]).mean_ns, 1);
+    assert_eq!(stats(&[2, 2, 3, 3, 3]).mean_ns, 2);
+}
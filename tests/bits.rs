@@ -0,0 +1,208 @@
+//! Round trips BITS packets from day 16 through the encoder and decoder.
+
+use advent_of_code_2021::day16::{
+    decode, encode, BitReader, BitReaderError, Contents, Header, LengthTypeId, Packet, TypeId,
+    MAX_DEPTH,
+};
+use num_bigint::BigUint;
+use proptest::prelude::*;
+
+const OPERATORS: [TypeId; 7] = [
+    TypeId::Sum,
+    TypeId::Product,
+    TypeId::Minimum,
+    TypeId::Maximum,
+    TypeId::GreaterThan,
+    TypeId::LessThan,
+    TypeId::EqualTo,
+];
+
+fn literal(version: u64, contents: Contents) -> Packet {
+    Packet {
+        header: Header {
+            version,
+            type_id: TypeId::Literal,
+        },
+        contents,
+    }
+}
+
+/// Always more than `u64::MAX`, since the decoder only uses `BigLiteral` for those.
+fn big_literal() -> impl Strategy<Value = BigUint> {
+    (1..=u64::MAX, prop::collection::vec(any::<u64>(), 1..3)).prop_map(|(high, rest)| {
+        rest.into_iter().fold(BigUint::from(high), |accum, limb| {
+            &(&accum << 64usize) + &BigUint::from(limb)
+        })
+    })
+}
+
+fn leaf() -> impl Strategy<Value = Packet> {
+    prop_oneof![
+        4 => (0..8u64, any::<u64>()).prop_map(|(version, value)| literal(version, Contents::Literal(value))),
+        1 => (0..8u64, big_literal()).prop_map(|(version, value)| literal(version, Contents::BigLiteral(value))),
+    ]
+}
+
+fn packet() -> impl Strategy<Value = Packet> {
+    leaf().prop_recursive(4, 32, 4, |inner| {
+        (
+            0..8u64,
+            prop::sample::select(OPERATORS.to_vec()),
+            prop_oneof![Just(LengthTypeId::Bits), Just(LengthTypeId::Packets)],
+            prop::collection::vec(inner, 1..4),
+        )
+            .prop_map(|(version, type_id, length_type_id, packets)| Packet {
+                header: Header { version, type_id },
+                contents: Contents::Operator {
+                    length_type_id,
+                    packets,
+                },
+            })
+    })
+}
+
+#[test]
+fn examples_round_trip() {
+    let examples = [
+        "D2FE28",
+        "38006F45291200",
+        "EE00D40C823060",
+        "8A004A801A8002F478",
+        "620080001611562C8802118E34",
+        "C0015000016115A2E0802F182340",
+        "A0016C880162017C3686B18A3D4780",
+    ];
+    for hex in examples {
+        let packet = decode(hex).unwrap();
+        assert_eq!(
+            decode(&encode(&packet).unwrap()).unwrap(),
+            packet,
+            "{}",
+            hex
+        );
+    }
+}
+
+#[test]
+fn literal_uses_fewest_groups() {
+    let packet = decode("D2FE28").unwrap();
+    assert_eq!(encode(&packet).unwrap(), "D2FE28");
+}
+
+#[test]
+fn lowercase_hex() {
+    assert_eq!(
+        decode("8a004a801a8002f478").unwrap(),
+        decode("8A004A801A8002F478").unwrap()
+    );
+}
+
+#[test]
+fn bit_reader_tracks_position() {
+    let mut reader = BitReader::new("D2FE28\n".as_bytes());
+    assert_eq!(reader.read_bits(3).unwrap(), 6);
+    assert_eq!(reader.read_bits(3).unwrap(), 4);
+    assert!(reader.read_bool().unwrap());
+    assert_eq!(reader.position(), 7);
+    assert_eq!(reader.read_bits(17).unwrap(), 0b0_1111_1110_0010_1000);
+    assert!(matches!(
+        reader.read_bits(1),
+        Err(BitReaderError::Truncated {
+            position: 24,
+            wanted: 1
+        })
+    ));
+}
+
+#[test]
+fn bit_reader_rejects_invalid_characters() {
+    let mut reader = BitReader::new("D2G".as_bytes());
+    assert_eq!(reader.read_bits(8).unwrap(), 0xD2);
+    assert!(matches!(
+        reader.read_bits(4),
+        Err(BitReaderError::InvalidCharacter {
+            index: 2,
+            character: 'G'
+        })
+    ));
+}
+
+#[test]
+fn truncated_transmission() {
+    let e = decode("8A004A801A80").unwrap_err();
+    assert!(e.downcast_ref::<BitReaderError>().is_some(), "{:#}", e);
+}
+
+#[test]
+fn overflow() {
+    let packet = Packet {
+        header: Header {
+            version: 0,
+            type_id: TypeId::Product,
+        },
+        contents: Contents::Operator {
+            length_type_id: LengthTypeId::Packets,
+            packets: vec![
+                literal(0, Contents::Literal(u64::MAX)),
+                literal(0, Contents::Literal(2)),
+            ],
+        },
+    };
+    let packet = decode(&encode(&packet).unwrap()).unwrap();
+
+    assert!(packet.value().is_err());
+    assert_eq!(
+        packet.big_value().unwrap().to_string(),
+        "36893488147419103230"
+    );
+}
+
+#[test]
+fn big_literal_decodes() {
+    // 17 groups of 4 bits, one more than fits in a u64
+    let mut hex = encode(&literal(0, Contents::Literal(u64::MAX))).unwrap();
+    let packet = decode(&hex).unwrap();
+    assert_eq!(packet.value().unwrap(), u64::MAX);
+
+    let big = &(&BigUint::from(u64::MAX) << 4usize) + &BigUint::from(0xFu64);
+    hex = encode(&literal(0, Contents::BigLiteral(big.clone()))).unwrap();
+    let packet = decode(&hex).unwrap();
+    assert_eq!(packet.contents, Contents::BigLiteral(big));
+    assert!(packet.value().is_err());
+    assert_eq!(
+        packet.big_value().unwrap().to_string(),
+        "295147905179352825855"
+    );
+}
+
+/// A literal inside `depth` sums with one sub-packet each.
+fn nested(depth: usize) -> Packet {
+    (0..depth).fold(literal(0, Contents::Literal(7)), |packet, _| Packet {
+        header: Header {
+            version: 1,
+            type_id: TypeId::Sum,
+        },
+        contents: Contents::Operator {
+            length_type_id: LengthTypeId::Packets,
+            packets: vec![packet],
+        },
+    })
+}
+
+#[test]
+fn nesting_limit() {
+    let packet = decode(&encode(&nested(MAX_DEPTH)).unwrap()).unwrap();
+    assert_eq!(packet.value().unwrap(), 7);
+    assert_eq!(packet.version_sum(), MAX_DEPTH as u64);
+
+    let e = decode(&encode(&nested(MAX_DEPTH + 1)).unwrap()).unwrap_err();
+    assert!(e.to_string().contains("nested more than"), "{:#}", e);
+}
+
+proptest! {
+    #[test]
+    fn decode_inverts_encode(packet in packet()) {
+        let hex = encode(&packet).unwrap();
+        prop_assert_eq!(decode(&hex).unwrap(), packet);
+    }
+}
@@ -0,0 +1,110 @@
+//! Checks that the input cache notices cached inputs changing after they were imported.
+
+use advent_of_code_2021::cache::{checksum, normalize, InputCache, Status, YEAR};
+use std::fs;
+use std::path::PathBuf;
+
+/// A fresh directory for one test, removed when it's dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> TempDir {
+        let dir = std::env::temp_dir().join(format!(
+            "advent_of_code_2021-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+
+    fn path(&self, name: &str) -> String {
+        self.0.join(name).display().to_string()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn normalize_line_endings() {
+    assert_eq!(normalize("a\r\nb\r\n"), "a\nb\n");
+    assert_eq!(normalize("a\rb\r"), "a\nb\n");
+    assert_eq!(normalize("a\r\n\rb"), "a\n\nb\n");
+}
+
+#[test]
+fn normalize_trailing_newlines() {
+    assert_eq!(normalize("a"), "a\n");
+    assert_eq!(normalize("a\n\n\n"), "a\n");
+    assert_eq!(normalize("a\n\nb\n"), "a\n\nb\n");
+    assert_eq!(normalize(""), "\n");
+}
+
+#[test]
+fn checksum_ignores_line_endings_after_normalizing() {
+    assert_eq!(
+        checksum(normalize("1\r\n2\r\n\r\n").as_bytes()),
+        checksum(normalize("1\n2").as_bytes())
+    );
+    assert!(checksum(b"1\n").starts_with("sha256:"));
+    assert_ne!(checksum(b"1\n"), checksum(b"2\n"));
+}
+
+#[test]
+fn status() {
+    let dir = TempDir::new("cache-status");
+    let source = dir.path("day1.txt");
+    fs::write(&source, "199\r\n200\r\n\r\n").unwrap();
+    let cache_dir = dir.path("cache");
+
+    let mut cache = InputCache::open(&cache_dir, YEAR, "default").unwrap();
+    assert_eq!(cache.status(1).unwrap(), Status::Missing);
+
+    let path = cache.import(1, &source).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "199\n200\n");
+    assert_eq!(cache.status(1).unwrap(), Status::Ok);
+    assert_eq!(cache.status(2).unwrap(), Status::Missing);
+
+    // the manifest is saved, so a new cache sees the same entry
+    let reopened = InputCache::open(&cache_dir, YEAR, "default").unwrap();
+    assert_eq!(reopened.status(1).unwrap(), Status::Ok);
+    assert_eq!(
+        reopened
+            .entries()
+            .iter()
+            .map(|(day, _)| *day)
+            .collect::<Vec<u32>>(),
+        vec![1]
+    );
+
+    fs::write(&path, "199\n201\n").unwrap();
+    assert_eq!(cache.status(1).unwrap(), Status::Modified);
+
+    // re-importing takes the edited file as the new original
+    let edited = dir.path("edited.txt");
+    fs::write(&edited, "199\n201\n").unwrap();
+    cache.import(1, &edited).unwrap();
+    assert_eq!(cache.status(1).unwrap(), Status::Ok);
+
+    fs::remove_file(&path).unwrap();
+    assert_eq!(cache.status(1).unwrap(), Status::Missing);
+}
+
+#[test]
+fn profiles_are_separate() {
+    let dir = TempDir::new("cache-profiles");
+    let source = dir.path("input.txt");
+    fs::write(&source, "1\n").unwrap();
+    let cache_dir = dir.path("cache");
+
+    let mut cache = InputCache::open(&cache_dir, YEAR, "alice").unwrap();
+    cache.import(3, &source).unwrap();
+
+    let other = InputCache::open(&cache_dir, YEAR, "bob").unwrap();
+    assert_eq!(other.status(3).unwrap(), Status::Missing);
+}
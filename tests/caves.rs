@@ -0,0 +1,53 @@
+//! Checks that day 12 rejects cave systems it can't count the paths through.
+
+use advent_of_code_2021::day12::{Day12, Graph};
+use advent_of_code_2021::io::PuzzleInput;
+use advent_of_code_2021::solver::Solver;
+
+fn parse(input: &str) -> anyhow::Result<Graph> {
+    let input = PuzzleInput::from_contents("caves", input);
+    Day12::default().parse(input)
+}
+
+fn parse_error(input: &str) -> String {
+    format!("{:#}", parse(input).unwrap_err())
+}
+
+#[test]
+fn valid() {
+    assert!(parse("start-A\nA-b\nb-end\n").is_ok());
+}
+
+#[test]
+fn duplicate_tunnels_are_ignored() {
+    let day12 = Day12::default();
+    let once = day12.part2(parse("start-A\nA-b\nb-end\n").unwrap());
+    let twice = day12.part2(parse("start-A\nA-b\nb-A\nb-end\n").unwrap());
+    assert_eq!(once.unwrap(), twice.unwrap());
+}
+
+#[test]
+fn missing_start_or_end() {
+    assert!(parse_error("A-b\nb-end\n").contains("no 'start' cave"));
+    assert!(parse_error("start-A\nA-b\n").contains("no 'end' cave"));
+}
+
+#[test]
+fn malformed_tunnels() {
+    assert!(parse_error("start-A\nAb\n").contains("caves:2:1: Expected '-'"));
+    assert!(parse_error("start-\n").contains("Expected a cave name"));
+    assert!(parse_error("start-A-end\n").contains("caves:1:8: Cave names can only have letters"));
+    assert!(parse_error("start-Ab\n").contains("all uppercase"));
+}
+
+#[test]
+fn self_loops() {
+    assert!(parse_error("start-b\nb-b\nb-end\n").contains("leads back to itself"));
+}
+
+#[test]
+fn big_caves_next_to_each_other() {
+    let error = parse_error("start-A\nA-B\nB-end\n");
+    assert!(error.contains("caves:2:1"), "{}", error);
+    assert!(error.contains("infinitely many paths"), "{}", error);
+}
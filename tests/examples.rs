@@ -0,0 +1,120 @@
+//! Runs every day's puzzle examples through the same registry and runner that `main` uses.
+//!
+//! Besides the examples registered by each day, any `input/day<N>-<variant>.input` file with a
+//! matching `input/day<N>-<variant>.answers.toml` sidecar is checked as well. The sidecar holds
+//! the expected answers, e.g.
+//!
+//! ```toml
+//! part1 = "40"
+//! part2 = "315"
+//! ```
+
+use advent_of_code_2021::io::PuzzleInput;
+use advent_of_code_2021::runner::{run, run_input, Run};
+use advent_of_code_2021::solver::{Part, Registry};
+use config::{Config, File, FileFormat};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const PARTS: [Part; 2] = [Part::One, Part::Two];
+
+fn check_run(run: &Run, expected: &str, source: &str) -> Option<String> {
+    match &run.answer {
+        Ok(answer) if answer.to_string() == expected => None,
+        Ok(answer) => Some(format!(
+            "day {} part {} ({}): expected {} but got {}",
+            run.day, run.part, source, expected, answer
+        )),
+        Err(e) => Some(format!(
+            "day {} part {} ({}): {:#}",
+            run.day, run.part, source, e
+        )),
+    }
+}
+
+#[test]
+fn examples() {
+    let registry = Registry::default();
+
+    let mut failures = Vec::new();
+    for day in registry.days() {
+        let solver = registry.get(day).unwrap();
+        for (i, example) in solver.examples().iter().enumerate() {
+            for part in PARTS {
+                let expected = match example.expected(part) {
+                    Some(expected) => expected,
+                    None => continue,
+                };
+                let source = format!("example {}", i + 1);
+                let input = PuzzleInput::from_contents(&source, example.input);
+                let run = run_input(solver, day, part, input);
+                failures.extend(check_run(&run, &expected.to_string(), &source));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+/// Parses `day<N>-<variant>.input` into the day number.
+fn variant_day(file_name: &str) -> Option<u32> {
+    let name = file_name.strip_prefix("day")?.strip_suffix(".input")?;
+    let (day, _variant) = name.split_once('-')?;
+    day.parse().ok()
+}
+
+#[test]
+fn input_variants() {
+    let registry = Registry::default();
+
+    let mut failures = Vec::new();
+    for entry in fs::read_dir("input").unwrap() {
+        let path = entry.unwrap().path();
+        let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let day = match variant_day(&file_name) {
+            Some(day) => day,
+            None => continue,
+        };
+
+        let sidecar = path.with_extension("answers.toml");
+        if !Path::new(&sidecar).exists() {
+            continue;
+        }
+        let mut settings = Config::default();
+        settings
+            .merge(File::new(sidecar.to_str().unwrap(), FileFormat::Toml))
+            .unwrap();
+        let expected: HashMap<String, String> = settings.try_into().unwrap();
+
+        let solver = match registry.get(day) {
+            Some(solver) => solver,
+            None => {
+                failures.push(format!("{}: day {} is not registered", file_name, day));
+                continue;
+            }
+        };
+        for part in PARTS {
+            let expected = match expected.get(&format!("part{}", part)) {
+                Some(expected) => expected,
+                None => continue,
+            };
+            let run = run(solver, day, part, Some(&path.to_str().unwrap().to_owned()));
+            failures.extend(check_run(&run, expected, &file_name));
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn every_day_has_examples() {
+    let registry = Registry::default();
+
+    let missing = registry
+        .days()
+        .filter(|day| registry.get(*day).unwrap().examples().is_empty())
+        .collect::<Vec<u32>>();
+
+    assert!(missing.is_empty(), "days without examples: {:?}", missing);
+}
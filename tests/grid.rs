@@ -0,0 +1,178 @@
+//! Checks the edges of the shared grid.
+
+use advent_of_code_2021::common::grid::Grid;
+use advent_of_code_2021::common::parse::ParseError;
+use advent_of_code_2021::common::point::{Point2, Position};
+use advent_of_code_2021::io::PuzzleInput;
+
+fn p(x: usize, y: usize) -> Position {
+    Position::new(x, y)
+}
+
+/// 0 1 2
+/// 3 4 5
+fn grid() -> Grid<usize> {
+    Grid::from_fn(3, 2, |p| p.y * 3 + p.x)
+}
+
+fn sorted(positions: impl Iterator<Item = Position>) -> Vec<(usize, usize)> {
+    let mut positions = positions.map(|p| (p.x, p.y)).collect::<Vec<_>>();
+    positions.sort_unstable();
+    positions
+}
+
+fn digits(text: &str) -> anyhow::Result<Grid<u8>> {
+    Grid::from_digit_lines(PuzzleInput::from_contents("grid", text))
+}
+
+#[test]
+fn neighbors4_at_corners_and_edges() {
+    let grid = grid();
+    assert_eq!(sorted(grid.neighbors4(p(0, 0))), vec![(0, 1), (1, 0)]);
+    assert_eq!(sorted(grid.neighbors4(p(2, 1))), vec![(1, 1), (2, 0)]);
+    assert_eq!(
+        sorted(grid.neighbors4(p(1, 0))),
+        vec![(0, 0), (1, 1), (2, 0)]
+    );
+}
+
+#[test]
+fn neighbors8_at_corners_and_edges() {
+    let grid = grid();
+    assert_eq!(
+        sorted(grid.neighbors8(p(0, 0))),
+        vec![(0, 1), (1, 0), (1, 1)]
+    );
+    assert_eq!(
+        sorted(grid.neighbors8(p(2, 0))),
+        vec![(1, 0), (1, 1), (2, 1)]
+    );
+    assert_eq!(
+        sorted(grid.neighbors8(p(1, 1))),
+        vec![(0, 0), (0, 1), (1, 0), (2, 0), (2, 1)]
+    );
+}
+
+#[test]
+fn neighbors_of_single_cell() {
+    let grid = Grid::new(1, 1, vec![0]);
+    assert_eq!(grid.neighbors4(p(0, 0)).count(), 0);
+    assert_eq!(grid.neighbors8(p(0, 0)).count(), 0);
+}
+
+#[test]
+fn offset_stays_inside() {
+    let grid = grid();
+    assert_eq!(grid.offset(p(0, 0), Point2::UP), None);
+    assert_eq!(grid.offset(p(0, 0), Point2::LEFT), None);
+    assert_eq!(grid.offset(p(2, 1), Point2::RIGHT), None);
+    assert_eq!(grid.offset(p(2, 1), Point2::DOWN), None);
+    assert_eq!(grid.offset(p(1, 1), Point2::new(-1, -1)), Some(p(0, 0)));
+    assert_eq!(grid.offset(p(usize::MAX, 0), Point2::RIGHT), None);
+}
+
+#[test]
+fn get_outside() {
+    let grid = grid();
+    assert_eq!(grid.get(p(2, 1)), Some(&5));
+    assert_eq!(grid.get(p(3, 0)), None);
+    assert_eq!(grid.get(p(0, 2)), None);
+}
+
+#[test]
+fn index_and_position_round_trip() {
+    let grid = grid();
+    for (i, position) in grid.positions().enumerate() {
+        assert_eq!(grid.index_of(position), i);
+        assert_eq!(grid.position_of(i), position);
+        assert_eq!(grid[position], i);
+    }
+}
+
+#[test]
+fn rows_and_columns() {
+    let grid = grid();
+    assert_eq!(
+        grid.rows().collect::<Vec<_>>(),
+        vec![&[0, 1, 2], &[3, 4, 5]]
+    );
+    assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![1, 4]);
+    assert_eq!(
+        grid.columns()
+            .map(|column| column.copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>(),
+        vec![vec![0, 3], vec![1, 4], vec![2, 5]]
+    );
+}
+
+#[test]
+fn one_row() {
+    let grid = Grid::new(3, 1, vec![1, 2, 3]);
+    assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[1, 2, 3]]);
+    assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3]);
+    assert_eq!(grid.columns().count(), 3);
+}
+
+#[test]
+fn one_column() {
+    let grid = Grid::new(1, 3, vec![1, 2, 3]);
+    assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[1], &[2], &[3]]);
+    assert_eq!(grid.column(0).copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(grid.columns().count(), 1);
+}
+
+#[test]
+fn empty() {
+    let grid = Grid::<u8>::new(0, 0, Vec::new());
+    assert!(grid.is_empty());
+    assert_eq!(grid.rows().count(), 0);
+    assert_eq!(grid.columns().count(), 0);
+    assert_eq!(grid.to_string(), "");
+
+    // rows with no columns are still rows
+    let grid = Grid::<u8>::new(0, 2, Vec::new());
+    assert_eq!(grid.rows().map(<[u8]>::len).collect::<Vec<_>>(), vec![0, 0]);
+    assert_eq!(grid.columns().count(), 0);
+
+    let grid = Grid::<u8>::new(2, 0, Vec::new());
+    assert_eq!(grid.rows().count(), 0);
+    assert_eq!(
+        grid.columns().map(Iterator::count).collect::<Vec<_>>(),
+        vec![0, 0]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Column 3 is outside the grid")]
+fn column_outside() {
+    let _ = grid().column(3);
+}
+
+#[test]
+fn from_rows_rejects_ragged_rows() {
+    let e = Grid::from_rows(vec![vec![1, 2], vec![3]]).unwrap_err();
+    assert_eq!(e.to_string(), "Row 2 has 1 cells but the first row has 2");
+}
+
+#[test]
+fn from_digit_lines() {
+    let grid = digits("123\n456\n").unwrap();
+    assert_eq!(grid, Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]));
+    assert!(digits("").unwrap().is_empty());
+}
+
+#[test]
+fn from_digit_lines_rejects_ragged_lines() {
+    for (text, column) in [("123\n45\n", 3), ("12\n456\n", 3)] {
+        let e = digits(text).unwrap_err();
+        let e = e.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((e.line, e.column), (2, column), "{}", e);
+        assert!(e.message.starts_with("Expected"), "{}", e);
+    }
+}
+
+#[test]
+fn display() {
+    assert_eq!(grid().to_string(), "012\n345");
+    assert_eq!(Grid::new(1, 2, vec!['#', '.']).to_string(), "#\n.");
+}
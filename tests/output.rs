@@ -0,0 +1,37 @@
+//! Checks the machine readable output formats.
+
+use advent_of_code_2021::output::{format_runs, OutputFormat};
+use advent_of_code_2021::runner::Run;
+use advent_of_code_2021::solver::{Answer, Part};
+
+fn run(day: u32, part: Part, answer: anyhow::Result<Answer>) -> Run {
+    Run {
+        day,
+        part,
+        answer,
+        parse_time: None,
+        solve_time: None,
+    }
+}
+
+fn runs() -> Vec<Run> {
+    vec![
+        run(1, Part::One, Ok(Answer::from(7u64))),
+        run(1, Part::Two, Err(anyhow::Error::msg("Bad input"))),
+        run(2, Part::One, Ok(Answer::from(-3i64))),
+    ]
+}
+
+#[test]
+fn plain_has_a_line_per_run() {
+    let output = format_runs(&runs(), OutputFormat::Plain).unwrap();
+    assert_eq!(output, "7\n\n-3");
+}
+
+#[test]
+fn tsv_reports_errors() {
+    let output = format_runs(&runs(), OutputFormat::Tsv).unwrap();
+    let lines = output.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[2], "1\t2\t\tBad input\t\t");
+}
@@ -0,0 +1,152 @@
+//! Checks that malformed input is reported with the file, line and column it's at.
+
+use advent_of_code_2021::common::parse::{InputLine, ParseError};
+use advent_of_code_2021::day14::Day14;
+use advent_of_code_2021::day3::Day3;
+use advent_of_code_2021::day4::Day4;
+use advent_of_code_2021::io::PuzzleInput;
+use advent_of_code_2021::solver::Solver;
+
+fn input(text: &str) -> PuzzleInput {
+    PuzzleInput::from_contents("input", text)
+}
+
+fn parse_error<S: Solver>(solver: S, text: &str) -> ParseError {
+    let e = solver
+        .parse(input(text))
+        .err()
+        .expect("should fail to parse");
+    e.downcast_ref::<ParseError>()
+        .unwrap_or_else(|| panic!("not a ParseError: {:#}", e))
+        .clone()
+}
+
+fn first_line(text: &str) -> InputLine {
+    input(text).lines().next().unwrap().unwrap()
+}
+
+#[test]
+fn lines_are_numbered_from_one() {
+    let mut lines = input("a\nb\n").lines();
+    assert_eq!(lines.next().unwrap().unwrap().number, 1);
+    assert_eq!(lines.next().unwrap().unwrap().number, 2);
+    assert!(lines.next().is_none());
+}
+
+#[test]
+fn column_of_sub_slice() {
+    let line = first_line("12 -> 345\n");
+    let (_, right) = line.text.split_once(" -> ").unwrap();
+    assert_eq!(line.column_of(right), 7);
+    assert_eq!(line.column_of(&line.text), 1);
+    // not part of the line, so falls back to the start
+    assert_eq!(line.column_of("345"), 1);
+}
+
+#[test]
+fn column_counts_characters() {
+    let line = first_line("é,x\n");
+    let (_, right) = line.text.split_once(',').unwrap();
+    assert_eq!(line.column_of(right), 3);
+}
+
+#[test]
+fn bad_number_points_at_field() {
+    let line = first_line("1,x2\n");
+    let (_, y) = line.split_once(&line.text, ",").unwrap();
+    let e = line.parse::<u32>(y).unwrap_err();
+    assert_eq!((e.line, e.column), (1, 3));
+    assert!(
+        e.to_string().starts_with("input:1:3: Unable to parse 'x2'"),
+        "{}",
+        e
+    );
+}
+
+#[test]
+fn caret_under_column() {
+    let line = first_line("1,x2\n");
+    let e = line.error_at(3, "Bad");
+    assert_eq!(e.to_string(), "input:1:3: Bad\n    1,x2\n      ^");
+}
+
+#[test]
+fn missing_separator() {
+    let line = first_line("12\n");
+    let e = line.split_once(&line.text, ",").unwrap_err();
+    assert_eq!(e.to_string(), "input:1:1: Expected ','\n    12\n    ^");
+}
+
+#[test]
+fn bad_digit() {
+    let line = first_line("12a4\n");
+    let e = line.digits::<u8>().unwrap_err();
+    assert_eq!(
+        (e.column, e.message.as_str()),
+        (3, "Expected a digit but got 'a'")
+    );
+}
+
+#[test]
+fn expect_line_at_end_of_input() {
+    let mut lines = input("only\n").lines();
+    assert_eq!(lines.expect_line().unwrap().text, "only");
+    let e = lines.expect_line().unwrap_err();
+    let e = e.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((e.line, e.column), (2, 1));
+    assert!(e
+        .to_string()
+        .starts_with("input:2:1: Unexpected end of input"));
+}
+
+#[test]
+fn day3_bad_bit() {
+    let e = parse_error(Day3, "0101\n0121\n");
+    assert_eq!((e.line, e.column), (2, 3));
+}
+
+#[test]
+fn day3_short_row() {
+    let e = parse_error(Day3, "01\n1111\n");
+    assert_eq!((e.line, e.column), (2, 3));
+
+    let e = parse_error(Day3, "0101\n11\n");
+    assert_eq!((e.line, e.column), (2, 3));
+    assert!(
+        e.to_string()
+            .starts_with("input:2:3: Expected 4 bits like the first line but got 2"),
+        "{}",
+        e
+    );
+}
+
+#[test]
+fn day4_ragged_board() {
+    let e = parse_error(Day4, "1\n\n2 1\n3\n");
+    assert_eq!((e.line, e.column), (4, 2));
+
+    let e = parse_error(Day4, "1\n\n2\n3  14\n");
+    assert_eq!((e.line, e.column), (4, 4));
+}
+
+#[test]
+fn day14_missing_rule() {
+    let e = parse_error(Day14, "NNX\n\nNN -> C\n");
+    assert_eq!((e.line, e.column), (1, 2));
+    assert_eq!(e.message, "There's no insertion rule for 'NX'");
+
+    let e = parse_error(Day14, "\n\nNN -> C\n");
+    assert_eq!((e.line, e.column), (1, 1));
+}
+
+#[test]
+fn day14_produced_pair_without_rule() {
+    let e = Day14
+        .part1(Day14.parse(input("NN\n\nNN -> X\n")).unwrap())
+        .unwrap_err();
+    assert!(
+        e.to_string().starts_with("There's no insertion rule for"),
+        "{}",
+        e
+    );
+}
@@ -0,0 +1,174 @@
+//! Checks parsing, distances and directions of the shared points.
+
+use advent_of_code_2021::common::parse::InputLine;
+use advent_of_code_2021::common::point::{Point2, Point3, Position};
+use advent_of_code_2021::io::PuzzleInput;
+use std::collections::HashSet;
+
+fn line(text: &str) -> InputLine {
+    PuzzleInput::from_contents("points", text)
+        .lines()
+        .next()
+        .unwrap()
+        .unwrap()
+}
+
+#[test]
+fn point2_from_input() {
+    let line = line("0,9 -> 5,9");
+    let (from, to) = line.text.split_once(" -> ").unwrap();
+    assert_eq!(
+        Point2::<u32>::from_input(&line, from).unwrap(),
+        Point2::new(0, 9)
+    );
+    assert_eq!(
+        Point2::<u32>::from_input(&line, to).unwrap(),
+        Point2::new(5, 9)
+    );
+}
+
+#[test]
+fn point2_from_input_wrong_field_count() {
+    let line = line("1,2 -> 3,4,5");
+    let (_, to) = line.text.split_once(" -> ").unwrap();
+    let e = Point2::<u32>::from_input(&line, to).unwrap_err();
+    assert_eq!(e.column, 8);
+    assert_eq!(e.message, "Expected 2 comma separated numbers");
+}
+
+#[test]
+fn point2_from_input_bad_number() {
+    let line = line("1,-2");
+    let e = Point2::<u32>::from_input(&line, &line.text).unwrap_err();
+    assert_eq!(e.column, 3);
+    assert!(e.message.starts_with("Unable to parse '-2'"), "{}", e);
+}
+
+#[test]
+fn point3_from_input() {
+    let line = line("1, -2 ,3");
+    assert_eq!(
+        Point3::<i32>::from_input(&line, &line.text).unwrap(),
+        Point3::new(1, -2, 3)
+    );
+    let e = Point3::<i32>::from_input(&line, &line.text[..5]).unwrap_err();
+    assert_eq!(e.message, "Expected 3 comma separated numbers");
+}
+
+#[test]
+fn from_str() {
+    assert_eq!("3,4".parse::<Point2<i64>>().unwrap(), Point2::new(3, 4));
+    assert_eq!(
+        " 1 , 2 , 3 ".parse::<Point3<u8>>().unwrap(),
+        Point3::new(1, 2, 3)
+    );
+
+    let e = "3".parse::<Point2<i64>>().unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "Expected 2 comma separated numbers but got '3'"
+    );
+    let e = "1,2".parse::<Point3<i64>>().unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "Expected 3 comma separated numbers but got '1,2'"
+    );
+    let e = "1,x".parse::<Point2<i64>>().unwrap_err();
+    assert!(e.to_string().starts_with("Unable to parse 'x'"), "{}", e);
+    assert!("1,256,0".parse::<Point3<u8>>().is_err());
+}
+
+#[test]
+fn display_round_trips() {
+    let p = Point3::new(-1, 0, 7);
+    assert_eq!(p.to_string().parse::<Point3<i32>>().unwrap(), p);
+    let p = Position::new(4, 2);
+    assert_eq!(p.to_string().parse::<Position>().unwrap(), p);
+}
+
+#[test]
+fn unsigned_distances() {
+    let a = Position::new(7, 1);
+    let b = Position::new(2, 4);
+    assert_eq!(a.manhattan_distance(b), 8);
+    assert_eq!(b.manhattan_distance(a), 8);
+    assert_eq!(a.chebyshev_distance(b), 5);
+    assert_eq!(b.chebyshev_distance(a), 5);
+    assert_eq!(a.manhattan_distance(a), 0);
+
+    let a = Point3::<u8>::new(0, 255, 10);
+    let b = Point3::<u8>::new(3, 250, 20);
+    assert_eq!(a.manhattan_distance(b), 18);
+    assert_eq!(b.manhattan_distance(a), 18);
+    assert_eq!(a.chebyshev_distance(b), 10);
+    assert_eq!(b.chebyshev_distance(a), 10);
+}
+
+#[test]
+fn signed_distances() {
+    let a = Point2::new(-3, 4);
+    let b = Point2::new(2, -1);
+    assert_eq!(a.manhattan_distance(b), 10);
+    assert_eq!(a.chebyshev_distance(b), 5);
+}
+
+#[test]
+fn signum() {
+    assert_eq!(Point2::new(-7, 0).signum(), Point2::new(-1, 0));
+    assert_eq!(Point2::new(3, -3).signum(), Point2::new(1, -1));
+    assert_eq!(Point2::<i32>::ORIGIN.signum(), Point2::ORIGIN);
+    assert_eq!(Point3::new(0, 5, -2).signum(), Point3::new(0, 1, -1));
+}
+
+#[test]
+fn directions() {
+    // y increases downwards
+    assert_eq!(Point2::<i32>::UP, Point2::new(0, -1));
+    assert_eq!(Point2::<i32>::DOWN, Point2::new(0, 1));
+    assert_eq!(Point2::<i32>::LEFT, Point2::new(-1, 0));
+    assert_eq!(Point2::<i32>::RIGHT, Point2::new(1, 0));
+    assert_eq!(Point2::<i32>::UP, -Point2::DOWN);
+    assert_eq!(Point2::<i32>::LEFT, -Point2::RIGHT);
+
+    for d in Point2::<i32>::ORTHOGONAL {
+        assert_eq!(d.manhattan_distance(Point2::ORIGIN), 1);
+    }
+
+    // every neighbour exactly once, in reading order
+    let all = Point2::<i32>::ALL_DIRECTIONS;
+    assert_eq!(all.iter().collect::<HashSet<_>>().len(), 8);
+    assert!(all.windows(2).all(|w| (w[0].y, w[0].x) < (w[1].y, w[1].x)));
+    for d in all {
+        assert_eq!(d.chebyshev_distance(Point2::ORIGIN), 1);
+    }
+    for d in Point2::<i32>::ORTHOGONAL {
+        assert!(all.contains(&d));
+    }
+
+    let orthogonal = Point3::<i32>::ORTHOGONAL;
+    assert_eq!(orthogonal.iter().collect::<HashSet<_>>().len(), 6);
+    for d in orthogonal {
+        assert_eq!(d.manhattan_distance(Point3::ORIGIN), 1);
+        assert!(orthogonal.contains(&-d));
+    }
+}
+
+#[test]
+fn neighbors() {
+    let p = Point2::new(5, 5);
+    assert_eq!(p.neighbors4().count(), 4);
+    assert!(p.neighbors8().all(|n| n.chebyshev_distance(p) == 1));
+    assert!(Point3::new(1, 1, 1)
+        .neighbors6()
+        .all(|n| n.manhattan_distance(Point3::new(1, 1, 1)) == 1));
+}
+
+#[test]
+fn try_cast() {
+    assert_eq!(
+        Point2::<isize>::new(3, 4).try_cast::<usize>(),
+        Some(Position::new(3, 4))
+    );
+    assert_eq!(Point2::<isize>::new(-1, 4).try_cast::<usize>(), None);
+    assert_eq!(Point3::<i64>::new(0, 0, 300).try_cast::<u8>(), None);
+}
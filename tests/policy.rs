@@ -0,0 +1,117 @@
+//! Checks the day 12 revisit policies given on the command line.
+
+use advent_of_code_2021::day12::{Day12, Graph, RevisitPolicy};
+use advent_of_code_2021::io::PuzzleInput;
+use advent_of_code_2021::solver::{Answer, Solver};
+use std::collections::{BTreeMap, BTreeSet};
+
+const SMALL_EXAMPLE: &str = "\
+start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end
+";
+
+fn policy(s: &str) -> RevisitPolicy {
+    s.parse().unwrap()
+}
+
+fn policy_error(s: &str) -> String {
+    format!("{:#}", s.parse::<RevisitPolicy>().unwrap_err())
+}
+
+fn graph() -> Graph {
+    let input = PuzzleInput::from_contents("caves", SMALL_EXAMPLE);
+    Day12::default().parse(input).unwrap()
+}
+
+fn count(policy: &str) -> anyhow::Result<u64> {
+    match Day12::new(false, Some(self::policy(policy)), None).part1(graph())? {
+        Answer::Unsigned(count) => Ok(count),
+        answer => panic!("Expected a count but got {:?}", answer),
+    }
+}
+
+#[test]
+fn presets() {
+    assert_eq!(policy(""), RevisitPolicy::default());
+    assert_eq!(policy("part1"), RevisitPolicy::part1());
+    assert_eq!(policy("part2"), RevisitPolicy::part2());
+    // a preset replaces everything before it
+    assert_eq!(policy("revisits=3, start, part2"), RevisitPolicy::part2());
+}
+
+#[test]
+fn settings() {
+    let policy = policy("part2, revisits=2, caves=3, revisits:b=0, forbid=c, start, end");
+    assert_eq!(
+        policy,
+        RevisitPolicy {
+            revisits: 2,
+            cave_revisits: BTreeMap::from([("b".to_owned(), 0)]),
+            max_revisited_caves: 3,
+            forbidden: BTreeSet::from(["c".to_owned()]),
+            revisit_start: true,
+            revisit_end: true,
+        }
+    );
+}
+
+#[test]
+fn unknown_setting() {
+    assert_eq!(policy_error("part3"), "Unrecognized policy setting 'part3'");
+    assert_eq!(
+        policy_error("part1,bogus=1"),
+        "Unrecognized policy setting 'bogus=1'"
+    );
+    // flags don't take values
+    assert_eq!(
+        policy_error("start=1"),
+        "Unrecognized policy setting 'start=1'"
+    );
+}
+
+#[test]
+fn missing_value() {
+    assert_eq!(
+        policy_error("revisits"),
+        "Policy setting 'revisits' needs a value"
+    );
+    assert_eq!(
+        policy_error("revisits:b"),
+        "Policy setting 'revisits:b' needs a value"
+    );
+    assert_eq!(
+        policy_error("forbid"),
+        "Unrecognized policy setting 'forbid'"
+    );
+    assert_eq!(
+        policy_error("caves=many"),
+        "Expected a number for 'caves' but got 'many'"
+    );
+}
+
+#[test]
+fn counts() {
+    assert_eq!(count("part1").unwrap(), 10);
+    assert_eq!(count("part2").unwrap(), 36);
+    // b can't be revisited, so only c and d can be
+    assert!(count("part2,revisits:b=0").unwrap() < 36);
+    // c is a dead end off A, so forbidding it only drops the paths through it
+    assert_eq!(count("forbid=c").unwrap(), 5);
+}
+
+#[test]
+fn unknown_cave() {
+    let e = format!("{:#}", count("forbid=x").unwrap_err());
+    assert!(e.contains("There's no 'x' cave"), "{}", e);
+}
+
+#[test]
+fn big_cave_limits_are_rejected() {
+    let e = format!("{:#}", count("revisits:A=0").unwrap_err());
+    assert!(e.contains("'A' is a big cave"), "{}", e);
+}
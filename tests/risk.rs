@@ -0,0 +1,81 @@
+//! Checks the lowest risk paths from day 15, and renders of them.
+
+use advent_of_code_2021::common::point::Position;
+use advent_of_code_2021::common::render::{render_path, RenderFormat};
+use advent_of_code_2021::day15::Day15;
+use advent_of_code_2021::io::PuzzleInput;
+use advent_of_code_2021::solver::{Answer, Part, Solver};
+
+fn solver(tile_factor: usize) -> Day15 {
+    Day15::new(tile_factor, None)
+}
+
+fn parse(input: &str) -> anyhow::Result<<Day15 as Solver>::Input> {
+    let input = PuzzleInput::from_contents("risk", input);
+    Day15::default().parse(input)
+}
+
+#[test]
+fn tile_factor_overflow() {
+    let grid = parse("12\n34\n").unwrap();
+    let e = solver(usize::MAX).part2(grid.clone()).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        format!("A 2x2 cave tiled {} times each way is too big", usize::MAX)
+    );
+
+    // each side fits, but not the area
+    let side = 1 << (usize::BITS / 2);
+    assert!(solver(side).part2(grid).is_err());
+}
+
+#[test]
+fn empty_cave() {
+    assert!(solver(5).part1(parse("").unwrap()).is_err());
+}
+
+#[test]
+fn path_is_returned() {
+    let grid = parse("19\n11\n").unwrap();
+    let route = Day15::default().lowest_risk_path(Part::One, &grid).unwrap();
+    assert_eq!(route.cost, 2);
+    assert_eq!(
+        route.path,
+        vec![
+            Position::new(0, 0),
+            Position::new(0, 1),
+            Position::new(1, 1)
+        ]
+    );
+
+    let route = solver(2).lowest_risk_path(Part::Two, &grid).unwrap();
+    assert_eq!(route.path.first(), Some(&Position::new(0, 0)));
+    assert_eq!(route.path.last(), Some(&Position::new(3, 3)));
+    assert_eq!(route.path.len(), 7);
+}
+
+#[test]
+fn ascii_render_is_plain_text() {
+    let path = [
+        Position::new(0, 0),
+        Position::new(0, 1),
+        Position::new(1, 1),
+    ];
+    let render = render_path(
+        (2, 2),
+        |p: Position| p.y * 2 + p.x,
+        &path,
+        RenderFormat::Ascii,
+        |_| 0,
+    );
+    assert_eq!(String::from_utf8(render).unwrap(), "[0] 1 \n[2][3]\n");
+}
+
+#[test]
+fn zero_risk_cells() {
+    let grid = parse("01\n11\n").unwrap();
+    assert_eq!(solver(2).part1(grid.clone()).unwrap(), Answer::from(2usize));
+    // a 0 goes up to 1 in the next tile, like any other risk
+    let route = solver(2).lowest_risk_path(Part::Two, &grid).unwrap();
+    assert_eq!(route.cost, 12);
+}
@@ -0,0 +1,245 @@
+//! Checks the generic searches on small hand-made graphs and grids.
+
+use advent_of_code_2021::common::grid::Grid;
+use advent_of_code_2021::common::point::Position;
+use advent_of_code_2021::common::search::{
+    all_paths, astar, bfs, dfs, dijkstra, dijkstra_dense, reachable, SearchResult,
+};
+use std::cell::Cell;
+
+/// Directed edges with their costs.
+const EDGES: [(char, char, u32); 6] = [
+    ('s', 'a', 1),
+    ('s', 'b', 5),
+    ('a', 'b', 1),
+    ('a', 'c', 7),
+    ('b', 'g', 1),
+    ('c', 'g', 1),
+];
+
+fn neighbors(state: &char) -> Vec<(char, u32)> {
+    EDGES
+        .iter()
+        .filter(|(from, _, _)| from == state)
+        .map(|(_, to, cost)| (*to, *cost))
+        .collect()
+}
+
+const RISKS: &str = "1163751742\n1381373672\n2136511328\n3694931569\n7463417111\n1319128137\n1359912421\n3125421639\n1293138521\n2311944581";
+
+fn risks() -> Grid<u32> {
+    let rows = RISKS
+        .lines()
+        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
+        .collect();
+    Grid::from_rows(rows).unwrap()
+}
+
+fn grid_neighbors(grid: &Grid<u32>, p: Position) -> impl Iterator<Item = (Position, u32)> + '_ {
+    grid.neighbors4(p).map(move |n| (n, grid[n]))
+}
+
+fn is_valid_path<C>(result: &SearchResult<char, C>, start: char, goal: char) -> bool {
+    result.path.first() == Some(&start)
+        && result.path.last() == Some(&goal)
+        && result
+            .path
+            .windows(2)
+            .all(|step| neighbors(&step[0]).iter().any(|(to, _)| *to == step[1]))
+}
+
+#[test]
+fn bfs_finds_fewest_steps() {
+    let result = bfs('s', neighbors, |s| *s == 'g').unwrap();
+    assert_eq!(result.path, vec!['s', 'b', 'g']);
+    assert_eq!(result.cost, 6);
+}
+
+#[test]
+fn dfs_finds_a_path() {
+    let result = dfs('s', neighbors, |s| *s == 'g').unwrap();
+    assert!(is_valid_path(&result, 's', 'g'), "{:?}", result.path);
+    let cost = result
+        .path
+        .windows(2)
+        .map(|step| {
+            neighbors(&step[0])
+                .iter()
+                .find(|(to, _)| *to == step[1])
+                .unwrap()
+                .1
+        })
+        .sum::<u32>();
+    assert_eq!(result.cost, cost);
+}
+
+#[test]
+fn dijkstra_finds_cheapest_path() {
+    let result = dijkstra('s', neighbors, |s| *s == 'g').unwrap();
+    assert_eq!(result.path, vec!['s', 'a', 'b', 'g']);
+    assert_eq!(result.cost, 3);
+}
+
+#[test]
+fn start_is_goal() {
+    let result = dijkstra('s', neighbors, |s| *s == 's').unwrap();
+    assert_eq!(result.path, vec!['s']);
+    assert_eq!(result.cost, 0);
+}
+
+#[test]
+fn no_path() {
+    let is_goal = |s: &char| *s == 'z';
+    assert!(bfs('s', neighbors, is_goal).is_none());
+    assert!(dfs('s', neighbors, is_goal).is_none());
+    assert!(dijkstra('s', neighbors, is_goal).is_none());
+    assert!(astar('s', neighbors, is_goal, |_| 0).is_none());
+    assert!(dijkstra_dense(4, 0, |_| Vec::<(usize, u32)>::new(), |s| s == 3).is_none());
+}
+
+#[test]
+fn astar_skips_stale_entries() {
+    // 'b' is first pushed at a cost of 5 from 's', then again at 2 through 'a', so the entry at 5
+    // is stale and must not expand 'b' a second time
+    let expanded_b = Cell::new(0);
+    let result = astar(
+        's',
+        |s| {
+            if *s == 'b' {
+                expanded_b.set(expanded_b.get() + 1);
+            }
+            neighbors(s)
+        },
+        |s| *s == 'g',
+        |_| 0,
+    )
+    .unwrap();
+    assert_eq!(result.path, vec!['s', 'a', 'b', 'g']);
+    assert_eq!(result.cost, 3);
+    assert_eq!(expanded_b.get(), 1);
+}
+
+#[test]
+fn astar_reopens_states_for_inconsistent_heuristics() {
+    // the estimate for 'b' is exact, so admissible, but much more than the step to 'a' plus the
+    // estimate for 'a', so 'a' is explored through the expensive tunnel before 'b' finds the
+    // cheaper way to it
+    let edges = [('s', 'a', 4), ('s', 'b', 1), ('b', 'a', 1), ('a', 'g', 4)];
+    let result = astar(
+        's',
+        |s| {
+            edges
+                .iter()
+                .filter(|(from, _, _)| from == s)
+                .map(|(_, to, cost)| (*to, *cost))
+                .collect::<Vec<(char, u32)>>()
+        },
+        |s| *s == 'g',
+        |s| if *s == 'b' { 5 } else { 0 },
+    )
+    .unwrap();
+    assert_eq!(result.path, vec!['s', 'b', 'a', 'g']);
+    assert_eq!(result.cost, 6);
+}
+
+#[test]
+fn astar_agrees_with_dijkstra() {
+    let grid = risks();
+    let goal = Position::new(grid.width() - 1, grid.height() - 1);
+
+    let dijkstra = dijkstra(
+        Position::new(0, 0),
+        |p| grid_neighbors(&grid, *p),
+        |p| *p == goal,
+    )
+    .unwrap();
+    let astar = astar(
+        Position::new(0, 0),
+        |p| grid_neighbors(&grid, *p),
+        |p| *p == goal,
+        |p| p.manhattan_distance(goal) as u32,
+    )
+    .unwrap();
+
+    assert_eq!(dijkstra.cost, 40);
+    assert_eq!(astar.cost, dijkstra.cost);
+    for result in [&dijkstra, &astar] {
+        assert_eq!(result.path.first(), Some(&Position::new(0, 0)));
+        assert_eq!(result.path.last(), Some(&goal));
+        let cost = result.path[1..].iter().map(|p| grid[*p]).sum::<u32>();
+        assert_eq!(cost, result.cost);
+    }
+}
+
+#[test]
+fn dijkstra_dense_agrees_with_dijkstra() {
+    let grid = risks();
+    let goal = grid.len() - 1;
+
+    let result = dijkstra_dense(
+        grid.len(),
+        0,
+        |i| {
+            grid_neighbors(&grid, grid.position_of(i))
+                .map(|(p, risk)| (grid.index_of(p), risk))
+                .collect::<Vec<(usize, u32)>>()
+        },
+        |i| i == goal,
+    )
+    .unwrap();
+
+    assert_eq!(result.cost, 40);
+    assert_eq!(result.path.first(), Some(&0));
+    assert_eq!(result.path.last(), Some(&goal));
+    let cost = result.path[1..]
+        .iter()
+        .map(|i| grid[grid.position_of(*i)])
+        .sum::<u32>();
+    assert_eq!(cost, result.cost);
+}
+
+#[test]
+fn dijkstra_dense_revisits_start() {
+    // a cycle back to the start mustn't overwrite its predecessor
+    let result = dijkstra_dense(3, 0, |i| vec![((i + 1) % 3, 1u32)], |i| i == 2).unwrap();
+    assert_eq!(result.path, vec![0, 1, 2]);
+    assert_eq!(result.cost, 2);
+}
+
+#[test]
+fn reachable_in_breadth_first_order() {
+    assert_eq!(reachable('s', neighbors), vec!['s', 'a', 'b', 'c', 'g']);
+    assert_eq!(reachable('g', neighbors), vec!['g']);
+}
+
+#[test]
+fn all_paths_visits_every_path() {
+    let mut paths = Vec::new();
+    let count = all_paths(
+        's',
+        neighbors,
+        |s| *s == 'g',
+        |path| paths.push(path.iter().collect::<String>()),
+    );
+    paths.sort();
+    assert_eq!(count, 3);
+    assert_eq!(paths, vec!["sabg", "sacg", "sbg"]);
+}
+
+#[test]
+fn all_paths_stops_at_goal() {
+    // the goal has neighbours, but no path continues past it
+    let count = all_paths(
+        0u32,
+        |s| {
+            if *s < 3 {
+                vec![(s + 1, ())]
+            } else {
+                Vec::new()
+            }
+        },
+        |s| *s == 1,
+        |path| assert_eq!(path, [0, 1]),
+    );
+    assert_eq!(count, 1);
+}